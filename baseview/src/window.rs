@@ -3,6 +3,7 @@ use baseview::{
     Event, EventStatus, Window, WindowHandle, WindowHandler, WindowOpenOptions, WindowScalePolicy,
 };
 use raw_window_handle::HasRawWindowHandle;
+use std::sync::mpsc::Receiver;
 
 use vizia_core::*;
 
@@ -42,6 +43,7 @@ impl ViziaWindow {
         win_desc: WindowDescription,
         app: F,
         on_idle: Option<Box<dyn Fn(&mut Context) + Send>>,
+        (proxy, proxy_receiver): (ContextProxy, Receiver<vizia_core::Event>),
     ) -> WindowHandle
     where
         P: HasRawWindowHandle,
@@ -68,6 +70,8 @@ impl ViziaWindow {
 
                 context.add_theme(DEFAULT_THEME);
 
+                context.set_proxy(proxy, proxy_receiver);
+
                 ViziaWindow::new(context, win_desc, window, Some(Box::new(app)), on_idle)
             },
         )
@@ -80,6 +84,7 @@ impl ViziaWindow {
         win_desc: WindowDescription,
         app: F,
         on_idle: Option<Box<dyn Fn(&mut Context) + Send>>,
+        (proxy, proxy_receiver): (ContextProxy, Receiver<vizia_core::Event>),
     ) -> WindowHandle
     where
         F: Fn(&mut Context),
@@ -104,6 +109,8 @@ impl ViziaWindow {
 
                 context.add_theme(DEFAULT_THEME);
 
+                context.set_proxy(proxy, proxy_receiver);
+
                 ViziaWindow::new(context, win_desc, window, Some(Box::new(app)), on_idle)
             },
         )
@@ -116,6 +123,7 @@ impl ViziaWindow {
         win_desc: WindowDescription,
         app: F,
         on_idle: Option<Box<dyn Fn(&mut Context) + Send>>,
+        (proxy, proxy_receiver): (ContextProxy, Receiver<vizia_core::Event>),
     ) where
         F: Fn(&mut Context),
         F: 'static + Send,
@@ -139,6 +147,8 @@ impl ViziaWindow {
 
                 context.add_theme(DEFAULT_THEME);
 
+                context.set_proxy(proxy, proxy_receiver);
+
                 ViziaWindow::new(context, win_desc, window, Some(Box::new(app)), on_idle)
             },
         )
@@ -1,4 +1,8 @@
 use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::sync::mpsc::Receiver;
+use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(feature = "clipboard")]
 use copypasta::ClipboardContext;
@@ -7,9 +11,10 @@ use femtovg::TextContext;
 // use unic_langid::LanguageIdentifier;
 
 use crate::{
-    storage::sparse_set::SparseSet, CachedData, Entity, Enviroment, Event, FontOrId, IdManager,
-    Message, ModelDataStore, Modifiers, MouseState, Propagation, ResourceManager, Style, Tree,
-    TreeExt, View, ViewHandler,
+    set_focus, storage::sparse_set::SparseSet, CachedData, ContextProxy, DragState, Entity,
+    Enviroment, Event, FontOrId, IdManager, Keymap, ListenerId, Listeners, MenuState, Message,
+    ModelDataStore, Modifiers, MouseState, PortalState, Propagation, ResourceManager, Style,
+    TaskHandle, Tasks, TimerId, Timers, TooltipState, Tree, TreeError, TreeExt, View, ViewHandler,
 };
 
 static DEFAULT_THEME: &str = include_str!("default_theme.css");
@@ -22,7 +27,16 @@ pub struct Context {
     pub views: HashMap<Entity, Box<dyn ViewHandler>>,
     pub data: SparseSet<ModelDataStore>,
     pub event_queue: VecDeque<Event>,
-    pub listeners: HashMap<Entity, Box<dyn Fn(&mut dyn ViewHandler, &mut Context, &mut Event)>>,
+    pub listeners: Listeners,
+    pub timers: Timers,
+    pub tasks: Tasks,
+    pub keymap: Keymap,
+    pub drag: DragState,
+    pub tooltip: TooltipState,
+    pub menus: MenuState,
+    pub portals: PortalState,
+    pub(crate) proxy: ContextProxy,
+    pub(crate) proxy_receiver: Receiver<Event>,
     pub style: Style,
     pub cache: CachedData,
 
@@ -48,6 +62,8 @@ impl Context {
         let mut cache = CachedData::default();
         cache.add(Entity::root()).expect("Failed to add entity to cache");
 
+        let (proxy, proxy_receiver) = ContextProxy::channel();
+
         Self {
             entity_manager: IdManager::new(),
             tree: Tree::new(),
@@ -59,7 +75,16 @@ impl Context {
             cache,
             enviroment: Enviroment::new(),
             event_queue: VecDeque::new(),
-            listeners: HashMap::default(),
+            listeners: Listeners::new(),
+            timers: Timers::new(),
+            tasks: Tasks::new(),
+            keymap: Keymap::new(),
+            drag: DragState::new(),
+            tooltip: TooltipState::new(),
+            menus: MenuState::new(),
+            portals: PortalState::new(),
+            proxy,
+            proxy_receiver,
             mouse: MouseState::default(),
             modifiers: Modifiers::empty(),
             captured: Entity::null(),
@@ -103,9 +128,102 @@ impl Context {
             self.cache.remove(*entity);
             self.style.remove(*entity);
             self.data.remove(*entity);
+            self.listeners.remove_entity(*entity);
+            self.timers.remove_entity(*entity);
+            self.tasks.remove_entity(*entity);
+            self.keymap.remove_entity(*entity);
+            self.drag.remove_entity(*entity);
+            self.tooltip.remove_entity(*entity);
+            self.menus.remove_entity(*entity);
+            self.portals.remove_entity(*entity);
+
+            if self.focused == *entity {
+                self.focused = Entity::root();
+            }
+
             self.entity_manager.destroy(*entity);
             self.views.remove(entity);
         }
+
+        // A tooltip is removed along with the view it was shown for
+        for overlay in self.tooltip.take_orphaned() {
+            self.remove(overlay);
+        }
+    }
+
+    /// Moves an entity, along with its descendants, to be the previous sibling of another entity.
+    ///
+    /// Unlike [Tree::insert_before], this also marks the style, layout and drawing as changed.
+    pub fn insert_before(&mut self, entity: Entity, sibling: Entity) -> Result<(), TreeError> {
+        self.tree.insert_before(entity, sibling)?;
+        self.tree_moved();
+
+        Ok(())
+    }
+
+    /// Moves an entity, along with its descendants, to be the next sibling of another entity.
+    ///
+    /// Unlike [Tree::insert_after], this also marks the style, layout and drawing as changed.
+    pub fn insert_after(&mut self, entity: Entity, sibling: Entity) -> Result<(), TreeError> {
+        self.tree.insert_after(entity, sibling)?;
+        self.tree_moved();
+
+        Ok(())
+    }
+
+    /// Moves an entity, along with its descendants, to be the child of a parent at an index.
+    ///
+    /// Unlike [Tree::move_to], this also marks the style, layout and drawing as changed.
+    pub fn move_to(
+        &mut self,
+        entity: Entity,
+        parent: Entity,
+        index: usize,
+    ) -> Result<(), TreeError> {
+        self.tree.move_to(entity, parent, index)?;
+        self.tree_moved();
+
+        Ok(())
+    }
+
+    /// Swaps the positions of two entities, along with their descendants.
+    ///
+    /// Unlike [Tree::swap], this also marks the style, layout and drawing as changed.
+    pub fn swap(&mut self, entity1: Entity, entity2: Entity) -> Result<(), TreeError> {
+        self.tree.swap(entity1, entity2)?;
+        self.tree_moved();
+
+        Ok(())
+    }
+
+    // Marks the style, layout and drawing as changed after entities are moved in the tree, since
+    // selectors, layout and draw order all depend on the position of an entity
+    fn tree_moved(&mut self) {
+        self.style.needs_restyle = true;
+        self.style.needs_relayout = true;
+        self.style.needs_redraw = true;
+    }
+
+    /// Runs a closure with an entity as the current entity, so that views built by the closure are
+    /// added after the existing children of the entity.
+    ///
+    /// This is used to build views outside of a build closure, such as in response to an event.
+    pub fn with_current<F, R>(&mut self, entity: Entity, f: F) -> R
+    where
+        F: FnOnce(&mut Context) -> R,
+    {
+        let prev_current = self.current;
+        let prev_count = self.count;
+
+        self.current = entity;
+        self.count = entity.child_iter(&self.tree).count();
+
+        let result = (f)(self);
+
+        self.current = prev_current;
+        self.count = prev_count;
+
+        result
     }
 
     /// Get stored data from the context.
@@ -144,19 +262,163 @@ impl Context {
         );
     }
 
-    pub fn add_listener<F, W>(&mut self, listener: F)
+    /// Sends a message to every view and model in the tree.
+    pub fn broadcast<M: Message>(&mut self, message: M) {
+        self.event_queue
+            .push_back(Event::new(message).origin(self.current).propagate(Propagation::Broadcast));
+    }
+
+    /// Sends a message to the target entity and all of its descendants.
+    pub fn emit_subtree<M: Message>(&mut self, target: Entity, message: M) {
+        self.event_queue.push_back(
+            Event::new(message).target(target).origin(self.current).propagate(Propagation::Subtree),
+        );
+    }
+
+    /// Moves keyboard focus to an entity, sending `FocusOut` and `FocusIn` events.
+    pub fn focus(&mut self, entity: Entity) {
+        set_focus(self, entity);
+    }
+
+    /// Adds a listener to the current entity, which receives every event before it is sent to its target.
+    ///
+    /// An entity can have any number of listeners, which are removed when the entity is removed.
+    pub fn add_listener<F, W>(&mut self, listener: F) -> ListenerId
     where
         W: View,
         F: 'static + Fn(&mut W, &mut Context, &mut Event),
     {
-        self.listeners.insert(
+        self.listeners.add(
             self.current,
             Box::new(move |event_handler, context, event| {
                 if let Some(widget) = event_handler.downcast_mut::<W>() {
                     (listener)(widget, context, event);
                 }
             }),
-        );
+        )
+    }
+
+    /// Removes a listener which was added with [Context::add_listener].
+    pub fn remove_listener(&mut self, id: ListenerId) {
+        self.listeners.remove(id);
+    }
+
+    /// Adds a timer which sends a message directly to the current entity after each interval.
+    ///
+    /// If `repeat` is false the message is sent once. The timer is cancelled when the entity is removed.
+    /// Repeating intervals shorter than `MIN_REPEAT_INTERVAL` are rounded up to it.
+    pub fn add_timer<M: Message + Clone>(
+        &mut self,
+        interval: Duration,
+        repeat: bool,
+        message: M,
+    ) -> TimerId {
+        let entity = self.current;
+
+        self.timers.add(
+            entity,
+            interval,
+            repeat,
+            Box::new(move || {
+                Some(
+                    Event::new(message.clone())
+                        .target(entity)
+                        .origin(entity)
+                        .propagate(Propagation::Direct),
+                )
+            }),
+        )
+    }
+
+    /// Sends a message directly to the current entity once the duration has elapsed.
+    pub fn emit_after<M: Message>(&mut self, duration: Duration, message: M) -> TimerId {
+        let entity = self.current;
+        let mut message = Some(message);
+
+        self.timers.add(
+            entity,
+            duration,
+            false,
+            Box::new(move || {
+                message.take().map(|message| {
+                    Event::new(message).target(entity).origin(entity).propagate(Propagation::Direct)
+                })
+            }),
+        )
+    }
+
+    /// Cancels a timer which was added with [Context::add_timer] or [Context::emit_after].
+    pub fn cancel_timer(&mut self, id: TimerId) {
+        self.timers.cancel(id);
+    }
+
+    /// Runs a closure on another thread and sends its result as a message directly to the current entity.
+    ///
+    /// The task is cancelled when the entity is removed, in which case the result is discarded.
+    pub fn spawn<F, M>(&mut self, f: F) -> TaskHandle
+    where
+        F: 'static + Send + FnOnce() -> M,
+        M: Message,
+    {
+        let entity = self.current;
+        let proxy = self.proxy.clone();
+        let task = self.tasks.add(entity);
+
+        self.tasks.run_task(task.clone(), f, move |message| {
+            let event =
+                Event::new(message).target(entity).origin(entity).propagate(Propagation::Direct);
+
+            // The context may have been dropped while the task was running
+            proxy.send_event(event).ok();
+        });
+
+        task
+    }
+
+    /// Polls a future to completion on another thread and sends its output as a message directly
+    /// to the current entity.
+    ///
+    /// The task is cancelled when the entity is removed, in which case the future is dropped. A
+    /// pending future only occupies one of the worker threads of [Tasks] while it is being polled.
+    pub fn spawn_future<Fut>(&mut self, future: Fut) -> TaskHandle
+    where
+        Fut: 'static + Send + Future,
+        Fut::Output: Message,
+    {
+        let entity = self.current;
+        let proxy = self.proxy.clone();
+        let task = self.tasks.add(entity);
+
+        self.tasks.run_future(task.clone(), future, move |message| {
+            let event =
+                Event::new(message).target(entity).origin(entity).propagate(Propagation::Direct);
+
+            // The context may have been dropped while the task was running
+            proxy.send_event(event).ok();
+        });
+
+        task
+    }
+
+    /// Returns a proxy which can send events to the context from any thread.
+    pub fn get_proxy(&self) -> ContextProxy {
+        self.proxy.clone()
+    }
+
+    /// Replaces the proxy of the context, along with the receiver for the events sent through it.
+    ///
+    /// This is called by backends which create a proxy before the context, such as the baseview
+    /// backend, where the context is created on the window thread.
+    pub fn set_proxy(&mut self, proxy: ContextProxy, receiver: Receiver<Event>) {
+        self.proxy = proxy;
+        self.proxy_receiver = receiver;
+    }
+
+    /// Sets the callback used to wake up the backend when an event is sent from another thread.
+    ///
+    /// This is called by backends which sleep while there are no OS events to process.
+    pub fn set_proxy_wakeup<F: 'static + Fn() + Send + Sync>(&mut self, wakeup: F) {
+        self.proxy.set_wakeup(Arc::new(wakeup));
     }
 
     pub fn emit_trace<M: Message>(&mut self, message: M) {
@@ -178,6 +440,9 @@ impl Context {
         }
 
         self.resource_manager.fonts.insert(name.to_owned(), FontOrId::Font(data.to_vec()));
+
+        #[cfg(feature = "software")]
+        self.resource_manager.font_data.insert(name.to_owned(), data.to_vec());
     }
 
     /// Sets the global default font for the application
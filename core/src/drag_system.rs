@@ -0,0 +1,413 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use morphorm::{PositionType, Units};
+
+use crate::{Abilities, Context, Entity, Event, Propagation, PseudoClass, TreeExt, VStack};
+
+/// Events which are sent during a drag and drop operation.
+///
+/// `DragStart` and `DragEnd` are sent directly to the view being dragged. The other events are sent
+/// directly to the drop target under the cursor, which is the nearest view containing the cursor
+/// that accepts the type of the dragged payload with `Handle::on_drop`. The payload can be read
+/// with [Context::drag_payload].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DragEvent {
+    // The view has started being dragged
+    DragStart,
+    // The drag operation has finished, either by dropping the payload or by being cancelled
+    DragEnd,
+    // The cursor has moved over the drop target while dragging
+    DragEnter,
+    // The cursor has left the drop target while dragging
+    DragLeave,
+    // The cursor has moved within the drop target to the given position while dragging
+    DragOver(f32, f32),
+    // The payload has been dropped onto the drop target
+    Drop,
+}
+
+// Creates the payload of a drag operation
+type PayloadFn = Box<dyn Fn(&Context) -> Box<dyn Any>>;
+
+// Receives a dropped payload, which is known to be of the accepted type
+type DropFn = Box<dyn Fn(&mut Context, Box<dyn Any>)>;
+
+// Receives the path of a file which was dropped from another application
+type FileDropFn = Box<dyn Fn(&mut Context, PathBuf)>;
+
+struct DragSource {
+    payload: PayloadFn,
+    preview: Option<Box<dyn Fn(&mut Context)>>,
+}
+
+struct DropTarget {
+    type_id: TypeId,
+    on_drop: DropFn,
+}
+
+// A drag operation which is in progress
+struct ActiveDrag {
+    source: Entity,
+    payload: Box<dyn Any>,
+    target: Entity,
+    preview: Entity,
+}
+
+/// Storage for the drag sources and drop targets of all entities, and the current drag operation.
+pub struct DragState {
+    /// The distance in pixels which the cursor must move while pressed on a draggable view before
+    /// a drag operation starts.
+    pub threshold: f32,
+    sources: HashMap<Entity, DragSource>,
+    targets: HashMap<Entity, Vec<DropTarget>>,
+    active: Option<ActiveDrag>,
+    file_targets: HashMap<Entity, FileDropFn>,
+    // The file drop target under the cursor while files are dragged over the window
+    file_target: Entity,
+}
+
+impl Default for DragState {
+    fn default() -> Self {
+        Self {
+            threshold: 4.0,
+            sources: HashMap::new(),
+            targets: HashMap::new(),
+            active: None,
+            file_targets: HashMap::new(),
+            file_target: Entity::null(),
+        }
+    }
+}
+
+impl DragState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Makes an entity draggable, with a function which creates the payload when a drag starts
+    pub(crate) fn add_source(&mut self, entity: Entity, payload: PayloadFn) {
+        let preview = self.sources.remove(&entity).and_then(|source| source.preview);
+        self.sources.insert(entity, DragSource { payload, preview });
+    }
+
+    // Sets the preview of a draggable entity
+    pub(crate) fn set_preview(&mut self, entity: Entity, preview: Box<dyn Fn(&mut Context)>) {
+        if let Some(source) = self.sources.get_mut(&entity) {
+            source.preview = Some(preview);
+        }
+    }
+
+    // Adds a callback for payloads of the given type which are dropped onto an entity
+    pub(crate) fn add_target(&mut self, entity: Entity, type_id: TypeId, on_drop: DropFn) {
+        let targets = self.targets.entry(entity).or_default();
+        targets.retain(|target| target.type_id != type_id);
+        targets.push(DropTarget { type_id, on_drop });
+    }
+
+    // Adds a callback for files which are dropped onto an entity from another application
+    pub(crate) fn add_file_target(&mut self, entity: Entity, on_file_drop: FileDropFn) {
+        self.file_targets.insert(entity, on_file_drop);
+    }
+
+    /// Removes the drag source and drop targets of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.sources.remove(&entity);
+        self.targets.remove(&entity);
+        self.file_targets.remove(&entity);
+
+        if self.file_target == entity {
+            self.file_target = Entity::null();
+        }
+
+        if let Some(active) = &mut self.active {
+            if active.target == entity {
+                active.target = Entity::null();
+            }
+        }
+    }
+
+    /// Returns true if a drag operation is in progress.
+    pub fn is_dragging(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Returns the entity which is being dragged, if a drag operation is in progress.
+    pub fn source(&self) -> Option<Entity> {
+        self.active.as_ref().map(|active| active.source)
+    }
+
+    // Returns the nearest entity containing the given entity which accepts the dragged payload
+    fn find_target(&self, cx: &Context, entity: Entity) -> Entity {
+        let type_id = match &self.active {
+            Some(active) => (*active.payload).type_id(),
+            None => return Entity::null(),
+        };
+
+        entity
+            .parent_iter(&cx.tree)
+            .find(|ancestor| {
+                self.targets
+                    .get(ancestor)
+                    .map_or(false, |targets| targets.iter().any(|target| target.type_id == type_id))
+            })
+            .unwrap_or(Entity::null())
+    }
+}
+
+impl Context {
+    /// Returns the payload of the current drag operation, if there is one and it has the given type.
+    pub fn drag_payload<T: 'static>(&self) -> Option<&T> {
+        self.drag.active.as_ref().and_then(|active| active.payload.downcast_ref::<T>())
+    }
+}
+
+/// Handles a mouse move while the left mouse button is pressed.
+///
+/// Starts a drag operation once the cursor has moved further than the drag threshold from where
+/// it was pressed on a draggable entity, and updates the drop target under the cursor.
+pub fn drag_move(cx: &mut Context) {
+    if !cx.drag.is_dragging() {
+        let (down_x, down_y) = cx.mouse.left.pos_down;
+        let (dx, dy) = (cx.mouse.cursorx - down_x, cx.mouse.cursory - down_y);
+
+        if (dx * dx + dy * dy).sqrt() < cx.drag.threshold {
+            return;
+        }
+
+        let pressed = cx.mouse.left.pressed;
+        let source = pressed
+            .parent_iter(&cx.tree)
+            .find(|ancestor| cx.drag.sources.contains_key(ancestor))
+            .unwrap_or(Entity::null());
+
+        if source == Entity::null() {
+            return;
+        }
+
+        start_drag(cx, source);
+    }
+
+    let (x, y) = (cx.mouse.cursorx, cx.mouse.cursory);
+
+    let preview = cx.drag.active.as_ref().map_or(Entity::null(), |active| active.preview);
+    if preview != Entity::null() {
+        cx.style.left.insert(preview, Units::Pixels(x));
+        cx.style.top.insert(preview, Units::Pixels(y));
+        cx.style.needs_relayout = true;
+        cx.style.needs_redraw = true;
+    }
+
+    let target = cx.drag.find_target(cx, cx.hovered);
+    set_target(cx, target);
+
+    if target != Entity::null() {
+        cx.event_queue.push_back(
+            Event::new(DragEvent::DragOver(x, y)).target(target).propagate(Propagation::Direct),
+        );
+    }
+}
+
+/// Drops the payload of the current drag operation onto the drop target under the cursor, if any.
+///
+/// Returns true if a drag operation was in progress.
+pub fn drag_drop(cx: &mut Context) -> bool {
+    let target = match &cx.drag.active {
+        Some(active) => active.target,
+        None => return false,
+    };
+
+    set_target(cx, Entity::null());
+
+    if let Some(active) = cx.drag.active.take() {
+        let type_id = (*active.payload).type_id();
+
+        // Take the callback out of the storage so that it can be called with a mutable context
+        let on_drop = cx.drag.targets.get_mut(&target).and_then(|targets| {
+            let index = targets.iter().position(|target| target.type_id == type_id)?;
+            Some(targets.remove(index))
+        });
+
+        if let Some(drop_target) = on_drop {
+            cx.with_current(target, |cx| (drop_target.on_drop)(cx, active.payload));
+
+            if cx.entity_manager.is_alive(target) {
+                cx.drag.targets.entry(target).or_default().push(drop_target);
+            }
+
+            cx.event_queue.push_back(
+                Event::new(DragEvent::Drop).target(target).propagate(Propagation::Direct),
+            );
+        }
+
+        end_drag(cx, active.source, active.preview);
+    }
+
+    true
+}
+
+/// Cancels the current drag operation without dropping its payload.
+pub fn drag_cancel(cx: &mut Context) {
+    if !cx.drag.is_dragging() {
+        return;
+    }
+
+    set_target(cx, Entity::null());
+
+    if let Some(active) = cx.drag.active.take() {
+        end_drag(cx, active.source, active.preview);
+    }
+}
+
+fn start_drag(cx: &mut Context, source: Entity) {
+    let payload = match cx.drag.sources.get(&source) {
+        Some(drag_source) => (drag_source.payload)(cx),
+        None => return,
+    };
+
+    // The preview is built as a child of the root so that it can follow the cursor anywhere
+    let mut preview = Entity::null();
+    if let Some(build_preview) = cx.drag.sources.get_mut(&source).and_then(|s| s.preview.take()) {
+        let (x, y) = (cx.mouse.cursorx, cx.mouse.cursory);
+
+        preview = cx.with_current(Entity::root(), |cx| {
+            VStack::new(cx, |_| {})
+                .position_type(PositionType::SelfDirected)
+                .left(Units::Pixels(x))
+                .top(Units::Pixels(y))
+                .width(Units::Auto)
+                .height(Units::Auto)
+                .z_order(i32::MAX)
+                .class("drag-preview")
+                .entity
+        });
+
+        cx.with_current(preview, |cx| (build_preview)(cx));
+
+        // The preview must not hide the drop targets under the cursor from hover detection
+        for entity in preview.branch_iter(&cx.tree).collect::<Vec<_>>() {
+            if let Some(abilities) = cx.style.abilities.get_mut(entity) {
+                abilities.set(Abilities::HOVERABLE, false);
+            }
+        }
+
+        if let Some(drag_source) = cx.drag.sources.get_mut(&source) {
+            drag_source.preview = Some(build_preview);
+        }
+    }
+
+    cx.drag.active = Some(ActiveDrag { source, payload, target: Entity::null(), preview });
+
+    cx.event_queue
+        .push_back(Event::new(DragEvent::DragStart).target(source).propagate(Propagation::Direct));
+}
+
+fn end_drag(cx: &mut Context, source: Entity, preview: Entity) {
+    if preview != Entity::null() {
+        cx.remove(preview);
+    }
+
+    if cx.entity_manager.is_alive(source) {
+        cx.event_queue.push_back(
+            Event::new(DragEvent::DragEnd).target(source).propagate(Propagation::Direct),
+        );
+    }
+}
+
+// Moves the :drag-over pseudo-class to a new drop target, and sends DragLeave and DragEnter events
+fn set_target(cx: &mut Context, target: Entity) {
+    let prev_target = match &mut cx.drag.active {
+        Some(active) if active.target != target => std::mem::replace(&mut active.target, target),
+        _ => return,
+    };
+
+    if prev_target != Entity::null() {
+        set_drag_over(cx, prev_target, false);
+
+        cx.event_queue.push_back(
+            Event::new(DragEvent::DragLeave).target(prev_target).propagate(Propagation::Direct),
+        );
+    }
+
+    if target != Entity::null() {
+        set_drag_over(cx, target, true);
+
+        cx.event_queue.push_back(
+            Event::new(DragEvent::DragEnter).target(target).propagate(Propagation::Direct),
+        );
+    }
+}
+
+/// Handles files being dragged over the window from another application.
+///
+/// The nearest view under the cursor which accepts files with `Handle::on_file_drop` is given the
+/// `:drag-over` pseudo-class.
+pub fn file_hover(cx: &mut Context) {
+    let target = cx
+        .hovered
+        .parent_iter(&cx.tree)
+        .find(|ancestor| cx.drag.file_targets.contains_key(ancestor))
+        .unwrap_or(Entity::null());
+
+    set_file_target(cx, target);
+}
+
+/// Handles files which were dragged over the window leaving it without being dropped.
+pub fn file_hover_cancel(cx: &mut Context) {
+    set_file_target(cx, Entity::null());
+}
+
+/// Passes a file which was dropped onto the window from another application to the callback of
+/// the nearest view under the cursor which accepts files.
+pub fn file_drop(cx: &mut Context, path: PathBuf) {
+    file_hover(cx);
+
+    let target = cx.drag.file_target;
+
+    set_file_target(cx, Entity::null());
+
+    // Take the callback out of the storage so that it can be called with a mutable context
+    if let Some(on_file_drop) = cx.drag.file_targets.remove(&target) {
+        cx.with_current(target, |cx| (on_file_drop)(cx, path));
+
+        if cx.entity_manager.is_alive(target) {
+            cx.drag.file_targets.entry(target).or_insert(on_file_drop);
+        }
+    }
+}
+
+// Moves the :drag-over pseudo-class to a new file drop target
+fn set_file_target(cx: &mut Context, target: Entity) {
+    let prev_target = std::mem::replace(&mut cx.drag.file_target, target);
+
+    if prev_target != target {
+        set_drag_over(cx, prev_target, false);
+        set_drag_over(cx, target, true);
+    }
+}
+
+fn set_drag_over(cx: &mut Context, entity: Entity, state: bool) {
+    if entity == Entity::null() {
+        return;
+    }
+
+    if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(entity) {
+        pseudo_classes.set(PseudoClass::DRAG_OVER, state);
+    }
+
+    cx.style.needs_restyle = true;
+    cx.style.needs_redraw = true;
+}
+
+// Wraps a callback for dropped payloads of type T
+pub(crate) fn drop_fn<T: 'static, F>(on_drop: F) -> DropFn
+where
+    F: 'static + Fn(&mut Context, T),
+{
+    Box::new(move |cx, payload| {
+        if let Ok(payload) = payload.downcast::<T>() {
+            (on_drop)(cx, *payload);
+        }
+    })
+}
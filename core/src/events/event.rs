@@ -6,14 +6,19 @@ use std::fmt::Debug;
 /// Determines how the event propagates through the tree
 #[derive(Debug, Clone, PartialEq)]
 pub enum Propagation {
-    // /// Events propagate down the tree to the target entity, e.g. from grand-parent to parent to child (target)
-    // Down,
+    /// Events propagate down the tree to the target entity, e.g. from grand-parent to parent to child (target).
+    /// Ancestors of the target receive the event in the capture phase, through the `event_capture()` method of the View trait.
+    Down,
     /// Events propagate up the tree from the target entity from ancestor to ancestor, e.g. from child (target) to parent to grand-parent etc...
     Up,
-    // /// Events propagate down the tree to the target entity and then back up to the root
-    // DownUp,
+    /// Events propagate down the tree to the target entity in the capture phase and then back up to the root
+    DownUp,
     /// Events propagate directly to the target entity and to no others
     Direct,
+    /// Events propagate to every entity in the tree, in tree order, regardless of the target
+    Broadcast,
+    /// Events propagate to the target entity and then to all of its descendants, in tree order
+    Subtree,
 }
 
 /// A message can be any static type.
@@ -60,7 +65,7 @@ impl<S: Debug + 'static + Send> Message for S {
 pub struct Event {
     // The entity that produced the event. Entity::null() for OS events or unspecified.
     pub origin: Entity,
-    // The entity the event should be sent to. Entity::null() to send to all entities.
+    // The entity the event should be sent to. Ignored by events with Broadcast propagation.
     pub target: Entity,
     // How the event propagates through the tree.
     pub propagation: Propagation,
@@ -68,8 +73,10 @@ pub struct Event {
     pub consumable: bool,
     // Determines whether the event should continue to be propagated
     pub(crate) consumed: bool,
-    // Specifies an order index which is used to sort the event queue
+    // Specifies an order index which is used to sort the event queue. Events with a lower order are dispatched first.
     pub order: i32,
+    // Whether the event should be dispatched after layout instead of at the start of the frame
+    pub deferred: bool,
 
     pub trace: bool,
 
@@ -99,6 +106,7 @@ impl Event {
             consumable: true,
             consumed: false,
             order: 0,
+            deferred: false,
             trace: false,
             message: Box::new(message),
         }
@@ -128,6 +136,21 @@ impl Event {
         self
     }
 
+    /// Sets the order of the event
+    ///
+    /// Events with a lower order are dispatched first, and events with the same order are
+    /// dispatched in the order they were sent.
+    pub fn order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Defers the event until after layout has been computed for the current frame
+    pub fn defer(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+
     pub fn direct(mut self, entity: Entity) -> Self {
         self.propagation = Propagation::Direct;
         self.target = entity;
@@ -1,5 +1,8 @@
 use crate::{Canvas, Context, Event};
 
+#[cfg(feature = "software")]
+use crate::SoftwareCanvas;
+
 use std::any::{Any, TypeId};
 
 pub trait ViewHandler: Any {
@@ -11,7 +14,12 @@ pub trait ViewHandler: Any {
 
     fn event(&mut self, cx: &mut Context, event: &mut Event);
 
+    fn event_capture(&mut self, cx: &mut Context, event: &mut Event);
+
     fn draw(&self, cx: &mut Context, canvas: &mut Canvas);
+
+    #[cfg(feature = "software")]
+    fn draw_software(&self, cx: &mut Context, canvas: &mut SoftwareCanvas);
 }
 
 impl dyn ViewHandler {
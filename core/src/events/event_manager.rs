@@ -10,15 +10,21 @@ pub struct EventManager {
     // Queue of events to be processed
     event_queue: Vec<Event>,
 
+    // Queue of events to be processed after layout
+    deferred_queue: Vec<Event>,
+
     // A copy of the tree for iteration
     tree: Tree,
 }
 
 impl EventManager {
     pub fn new() -> Self {
-        EventManager { event_queue: Vec::new(), tree: Tree::new() }
+        EventManager { event_queue: Vec::new(), deferred_queue: Vec::new(), tree: Tree::new() }
     }
 
+    /// Dispatches the events in the context event queue, in order of the `order` of each event.
+    ///
+    /// Deferred events are held back until the next call to [EventManager::flush_deferred_events].
     pub fn flush_events(&mut self, context: &mut Context) {
         // Clear the event queue in the event manager
         self.event_queue.clear();
@@ -26,7 +32,30 @@ impl EventManager {
         //state.removed_entities.clear();
 
         // Move events from state to event manager
-        self.event_queue.extend(context.event_queue.drain(0..));
+        for event in context.event_queue.drain(0..) {
+            if event.deferred {
+                self.deferred_queue.push(event);
+            } else {
+                self.event_queue.push(event);
+            }
+        }
+
+        self.dispatch_events(context);
+    }
+
+    /// Dispatches the events which were deferred by previous calls to [EventManager::flush_events].
+    pub fn flush_deferred_events(&mut self, context: &mut Context) {
+        self.event_queue.clear();
+
+        self.event_queue.append(&mut self.deferred_queue);
+
+        self.dispatch_events(context);
+    }
+
+    // Sends the events in the event manager queue to their targets
+    fn dispatch_events(&mut self, context: &mut Context) {
+        // Stable sort, so events with the same order are dispatched in the order they were sent
+        self.event_queue.sort_by_key(|event| event.order);
 
         if context.tree.changed {
             self.tree = context.tree.clone();
@@ -39,20 +68,21 @@ impl EventManager {
             }
 
             // Send events to any listeners
-            let listeners =
-                context.listeners.iter().map(|(entity, _)| *entity).collect::<Vec<Entity>>();
-            for entity in listeners {
-                if let Some(listener) = context.listeners.remove(&entity) {
+            for (id, entity) in context.listeners.ids() {
+                if let Some(listener) = context.listeners.take(id) {
                     if let Some(mut event_handler) = context.views.remove(&entity) {
                         let prev = context.current;
                         context.current = entity;
                         (listener)(event_handler.as_mut(), context, event);
                         context.current = prev;
 
-                        context.views.insert(entity, event_handler);
+                        // The view is not put back if its entity was removed while handling the event
+                        if context.entity_manager.is_alive(entity) {
+                            context.views.insert(entity, event_handler);
+                        }
                     }
 
-                    context.listeners.insert(entity, listener);
+                    context.listeners.restore(id, listener);
                 }
 
                 if event.consumed {
@@ -63,12 +93,61 @@ impl EventManager {
             // Define the target to prevent multiple mutable borrows error
             let target = event.target;
 
+            // Send broadcast and subtree events to each entity of the branch in tree order
+            if matches!(event.propagation, Propagation::Broadcast | Propagation::Subtree) {
+                let root = if event.propagation == Propagation::Broadcast {
+                    Entity::root()
+                } else {
+                    target
+                };
+
+                let branch = root.branch_iter(&self.tree).collect::<Vec<_>>();
+
+                for entity in branch {
+                    visit_entity(context, entity, event);
+
+                    if event.consumed {
+                        break;
+                    }
+                }
+
+                continue 'events;
+            }
+
+            // Propagate down from root to target (not including target) in the capture phase
+            if matches!(event.propagation, Propagation::Down | Propagation::DownUp) {
+                let ancestors = target
+                    .parent_iter(&self.tree)
+                    .filter(|entity| *entity != target)
+                    .collect::<Vec<_>>();
+
+                for entity in ancestors.into_iter().rev() {
+                    if let Some(mut view) = context.views.remove(&entity) {
+                        let prev = context.current;
+                        context.current = entity;
+                        view.event_capture(context, event);
+                        context.current = prev;
+
+                        if context.entity_manager.is_alive(entity) {
+                            context.views.insert(entity, view);
+                        }
+                    }
+
+                    // Skip to the next event if the current event is consumed
+                    if event.consumed {
+                        continue 'events;
+                    }
+                }
+            }
+
             // Send event to target
             if let Some(mut view) = context.views.remove(&event.target) {
                 context.current = event.target;
                 view.event(context, event);
 
-                context.views.insert(event.target, view);
+                if context.entity_manager.is_alive(event.target) {
+                    context.views.insert(event.target, view);
+                }
             }
 
             if let Some(mut model_list) = context.data.remove(event.target) {
@@ -89,7 +168,7 @@ impl EventManager {
             // }
 
             // Propagate up from target to root (not including target)
-            if event.propagation == Propagation::Up {
+            if matches!(event.propagation, Propagation::Up | Propagation::DownUp) {
                 // Walk up the tree from parent to parent
                 for entity in target.parent_iter(&self.tree) {
                     // Skip the target entity
@@ -104,7 +183,9 @@ impl EventManager {
                         view.event(context, event);
                         context.current = prev;
 
-                        context.views.insert(entity, view);
+                        if context.entity_manager.is_alive(entity) {
+                            context.views.insert(entity, view);
+                        }
                     }
 
                     if let Some(mut model_list) = context.data.remove(entity) {
@@ -128,3 +209,220 @@ impl EventManager {
         }
     }
 }
+
+// Sends an event to the view and models of an entity
+fn visit_entity(context: &mut Context, entity: Entity, event: &mut Event) {
+    let prev = context.current;
+    context.current = entity;
+
+    if let Some(mut view) = context.views.remove(&entity) {
+        view.event(context, event);
+
+        if context.entity_manager.is_alive(entity) {
+            context.views.insert(entity, view);
+        }
+    }
+
+    if let Some(mut model_list) = context.data.remove(entity) {
+        for (_, model) in model_list.data.iter_mut() {
+            model.event(context, event);
+        }
+
+        context.data.insert(entity, model_list).expect("Failed to insert data");
+    }
+
+    context.current = prev;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use super::*;
+    use crate::{Model, View};
+
+    #[derive(Debug)]
+    struct Record(u32);
+
+    #[derive(Default)]
+    struct Recorder {
+        received: Vec<u32>,
+    }
+
+    impl Model for Recorder {
+        fn event(&mut self, _: &mut Context, event: &mut Event) {
+            if let Some(Record(id)) = event.message.downcast() {
+                self.received.push(*id);
+            }
+        }
+    }
+
+    fn received(cx: &Context) -> &Vec<u32> {
+        &cx.data::<Recorder>().expect("Failed to get recorder").received
+    }
+
+    type Log = Rc<RefCell<Vec<String>>>;
+
+    // A view which logs the events it receives, optionally consuming them
+    struct Probe {
+        name: &'static str,
+        log: Log,
+        consume_capture: bool,
+        consume_event: bool,
+    }
+
+    impl View for Probe {
+        fn event_capture(&mut self, _: &mut Context, event: &mut Event) {
+            self.log.borrow_mut().push(format!("{} capture", self.name));
+
+            if self.consume_capture {
+                event.consume();
+            }
+        }
+
+        fn event(&mut self, _: &mut Context, event: &mut Event) {
+            self.log.borrow_mut().push(self.name.to_owned());
+
+            if self.consume_event {
+                event.consume();
+            }
+        }
+    }
+
+    // Adds a probe as the last child of the parent entity
+    fn probe(cx: &mut Context, parent: Entity, name: &'static str, log: &Log) -> Entity {
+        cx.with_current(parent, |cx| {
+            Probe { name, log: log.clone(), consume_capture: false, consume_event: false }
+                .build2(cx, |_| {})
+                .entity
+        })
+    }
+
+    // Builds a root -> a -> b -> c chain of probes
+    fn chain(cx: &mut Context, log: &Log) -> (Entity, Entity, Entity) {
+        cx.entity_manager.create();
+
+        let a = probe(cx, Entity::root(), "a", log);
+        let b = probe(cx, a, "b", log);
+        let c = probe(cx, b, "c", log);
+
+        (a, b, c)
+    }
+
+    #[test]
+    fn events_are_dispatched_in_order() {
+        let mut cx = Context::new();
+        Recorder::default().build(&mut cx);
+
+        cx.event_queue.push_back(Event::new(Record(0)).order(1));
+        cx.event_queue.push_back(Event::new(Record(1)));
+        cx.event_queue.push_back(Event::new(Record(2)).order(-1));
+        cx.event_queue.push_back(Event::new(Record(3)));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(received(&cx), &vec![2, 1, 3, 0]);
+    }
+
+    #[test]
+    fn deferred_events_are_held_back() {
+        let mut cx = Context::new();
+        Recorder::default().build(&mut cx);
+
+        cx.event_queue.push_back(Event::new(Record(0)).defer());
+        cx.event_queue.push_back(Event::new(Record(1)));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(received(&cx), &vec![1]);
+
+        event_manager.flush_deferred_events(&mut cx);
+
+        assert_eq!(received(&cx), &vec![1, 0]);
+    }
+
+    #[test]
+    fn down_up_events_are_captured_then_bubbled() {
+        let mut cx = Context::new();
+        let log = Log::default();
+        let (_, _, c) = chain(&mut cx, &log);
+
+        cx.event_queue.push_back(Event::new(Record(0)).target(c).propagate(Propagation::DownUp));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(*log.borrow(), vec!["a capture", "b capture", "c", "b", "a"]);
+    }
+
+    #[test]
+    fn consuming_in_capture_stops_propagation() {
+        let mut cx = Context::new();
+        let log = Log::default();
+        let (_, b, c) = chain(&mut cx, &log);
+
+        cx.views
+            .get_mut(&b)
+            .and_then(|view| view.downcast_mut::<Probe>())
+            .unwrap()
+            .consume_capture = true;
+
+        cx.event_queue.push_back(Event::new(Record(0)).target(c).propagate(Propagation::DownUp));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(*log.borrow(), vec!["a capture", "b capture"]);
+    }
+
+    #[test]
+    fn broadcast_events_reach_every_entity_in_tree_order() {
+        let mut cx = Context::new();
+        let log = Log::default();
+        chain(&mut cx, &log);
+        probe(&mut cx, Entity::root(), "d", &log);
+
+        cx.broadcast(Record(0));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(*log.borrow(), vec!["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn subtree_events_reach_only_the_target_and_its_descendants() {
+        let mut cx = Context::new();
+        let log = Log::default();
+        let (_, b, _) = chain(&mut cx, &log);
+        probe(&mut cx, Entity::root(), "d", &log);
+
+        cx.emit_subtree(b, Record(0));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(*log.borrow(), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn consuming_a_broadcast_event_stops_propagation() {
+        let mut cx = Context::new();
+        let log = Log::default();
+        let (_, b, _) = chain(&mut cx, &log);
+        probe(&mut cx, Entity::root(), "d", &log);
+
+        cx.views.get_mut(&b).and_then(|view| view.downcast_mut::<Probe>()).unwrap().consume_event =
+            true;
+
+        cx.broadcast(Record(0));
+
+        let mut event_manager = EventManager::new();
+        event_manager.flush_events(&mut cx);
+
+        assert_eq!(*log.borrow(), vec!["a", "b"]);
+    }
+}
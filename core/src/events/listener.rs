@@ -0,0 +1,106 @@
+use crate::{Context, Entity, Event, ViewHandler};
+
+/// A callback which receives every event before it is sent to its target.
+pub type Listener = Box<dyn Fn(&mut dyn ViewHandler, &mut Context, &mut Event)>;
+
+/// An identifier for a listener, returned by `Context::add_listener`.
+///
+/// The identifier can be passed to `Context::remove_listener` to remove the listener.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ListenerId(u64);
+
+struct ListenerEntry {
+    id: ListenerId,
+    entity: Entity,
+    // None while the listener is being called
+    listener: Option<Listener>,
+}
+
+/// Storage for the listeners of all entities, in the order they were added.
+#[derive(Default)]
+pub struct Listeners {
+    entries: Vec<ListenerEntry>,
+    next_id: u64,
+}
+
+impl Listeners {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a listener for an entity and returns its id.
+    pub fn add(&mut self, entity: Entity, listener: Listener) -> ListenerId {
+        let id = ListenerId(self.next_id);
+        self.next_id += 1;
+
+        self.entries.push(ListenerEntry { id, entity, listener: Some(listener) });
+
+        id
+    }
+
+    /// Removes a listener, returning true if the listener existed.
+    pub fn remove(&mut self, id: ListenerId) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|entry| entry.id != id);
+        self.entries.len() != len
+    }
+
+    /// Removes all of the listeners of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.entries.retain(|entry| entry.entity != entity);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of listeners of an entity.
+    pub fn count(&self, entity: Entity) -> usize {
+        self.entries.iter().filter(|entry| entry.entity == entity).count()
+    }
+
+    // Returns the ids and entities of all listeners, in the order they were added
+    pub(crate) fn ids(&self) -> Vec<(ListenerId, Entity)> {
+        self.entries.iter().map(|entry| (entry.id, entry.entity)).collect()
+    }
+
+    // Takes a listener out of the storage so that it can be called with a mutable context
+    pub(crate) fn take(&mut self, id: ListenerId) -> Option<Listener> {
+        self.entries.iter_mut().find(|entry| entry.id == id).and_then(|entry| entry.listener.take())
+    }
+
+    // Puts back a listener which was taken, unless it was removed in the meantime
+    pub(crate) fn restore(&mut self, id: ListenerId, listener: Listener) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.listener = Some(listener);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop() -> Listener {
+        Box::new(|_, _, _| {})
+    }
+
+    #[test]
+    fn multiple_listeners_per_entity() {
+        let mut listeners = Listeners::new();
+        let entity = Entity::new(1, 0);
+
+        let first = listeners.add(entity, noop());
+        let second = listeners.add(entity, noop());
+
+        assert_ne!(first, second);
+        assert_eq!(listeners.count(entity), 2);
+
+        assert!(listeners.remove(first));
+        assert!(!listeners.remove(first));
+        assert_eq!(listeners.ids(), vec![(second, entity)]);
+
+        listeners.remove_entity(entity);
+        assert!(listeners.is_empty());
+    }
+}
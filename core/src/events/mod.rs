@@ -36,6 +36,10 @@
 //!     }
 //! }
 //! ```
+//!
+//! Events with `Down` or `DownUp` propagation first pass through the ancestors of the target, from the root down, in a capture phase.
+//! Views receive these events through the `event_capture()` method of the View trait, and can consume an event before it reaches the target.
+//! Mouse and keyboard events from the window use `DownUp` propagation, so containers can intercept them before their children.
 
 mod event_manager;
 pub use event_manager::EventManager;
@@ -45,3 +49,6 @@ pub use event::{Event, Message, Propagation};
 
 mod event_handler;
 pub use event_handler::ViewHandler;
+
+mod listener;
+pub use listener::{Listener, ListenerId, Listeners};
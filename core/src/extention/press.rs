@@ -2,7 +2,42 @@ use std::marker::PhantomData;
 
 use morphorm::GeometryChanged;
 
-use crate::{Context, Entity, Event, Handle, MouseButton, View, ViewHandler, WindowEvent};
+use crate::style::PropGet;
+use crate::{
+    Code, Context, Entity, Event, Handle, MouseButton, PropSet, View, ViewHandler, WindowEvent,
+};
+
+/// Returns true if the key activates a focused pressable view, i.e. Enter or Space.
+pub fn is_activation_key(code: Code) -> bool {
+    matches!(code, Code::Enter | Code::NumpadEnter | Code::Space)
+}
+
+/// Handles Enter and Space for a focused pressable view, returning true if the view should run its
+/// action.
+///
+/// The view has the `:active` pseudo-class while the key is held, and repeated key presses while
+/// the key is held are ignored.
+pub fn activation_key_pressed(cx: &mut Context, event: &mut Event) -> bool {
+    if event.target != cx.current {
+        return false;
+    }
+
+    match event.message.downcast() {
+        Some(WindowEvent::KeyDown(code, _))
+            if is_activation_key(*code) && !cx.current.is_active(cx) =>
+        {
+            cx.current.set_active(cx, true);
+            true
+        }
+
+        Some(WindowEvent::KeyUp(code, _)) if is_activation_key(*code) => {
+            cx.current.set_active(cx, false);
+            false
+        }
+
+        _ => false,
+    }
+}
 
 // Press
 pub struct Press<V: View> {
@@ -13,6 +48,8 @@ pub struct Press<V: View> {
 }
 
 impl<V: View> Press<V> {
+    /// Runs the action when the view is clicked, or when Enter or Space is pressed while the view
+    /// is focused. The view is made focusable so that it can be reached with the Tab key.
     pub fn new<'a, F>(handle: Handle<'a, V>, action: F) -> Handle<'a, Press<V>>
     where
         F: 'static + Fn(&mut Context),
@@ -30,7 +67,7 @@ impl<V: View> Press<V> {
             }
         }
 
-        Handle { entity: handle.entity, p: Default::default(), cx: handle.cx }
+        Handle { entity: handle.entity, p: Default::default(), cx: handle.cx }.focusable(true)
     }
 }
 
@@ -42,6 +79,14 @@ impl<V: View> View for Press<V> {
     fn event(&mut self, cx: &mut Context, event: &mut Event) {
         self.view.event(cx, event);
 
+        if activation_key_pressed(cx, event) {
+            if let Some(action) = self.action.take() {
+                (action)(cx);
+
+                self.action = Some(action);
+            }
+        }
+
         if let Some(window_event) = event.message.downcast() {
             match window_event {
                 WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
@@ -0,0 +1,159 @@
+use crate::{
+    Abilities, Context, Display, Entity, Event, Propagation, PseudoClass, TreeExt, Visibility,
+    WindowEvent,
+};
+
+/// Moves keyboard focus to an entity.
+///
+/// The `:focus` pseudo-class is moved to the new entity, and `FocusOut` and `FocusIn` events are
+/// sent directly to the previously focused entity and the newly focused entity.
+pub fn set_focus(cx: &mut Context, entity: Entity) {
+    let prev_focused = cx.focused;
+
+    if prev_focused == entity {
+        return;
+    }
+
+    if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(prev_focused) {
+        pseudo_classes.set(PseudoClass::FOCUS, false);
+    }
+
+    if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(entity) {
+        pseudo_classes.set(PseudoClass::FOCUS, true);
+    }
+
+    cx.focused = entity;
+
+    cx.style.needs_restyle = true;
+    cx.style.needs_redraw = true;
+
+    if prev_focused != Entity::null() {
+        cx.event_queue.push_back(
+            Event::new(WindowEvent::FocusOut).target(prev_focused).propagate(Propagation::Direct),
+        );
+    }
+
+    if entity != Entity::null() {
+        cx.event_queue.push_back(
+            Event::new(WindowEvent::FocusIn).target(entity).propagate(Propagation::Direct),
+        );
+    }
+}
+
+/// Moves keyboard focus to the nearest focusable entity which contains the given entity, if any.
+///
+/// This is used to focus a view when it, or one of its descendants, is clicked.
+pub fn focus_nearest(cx: &mut Context, entity: Entity) {
+    if let Some(focusable) =
+        entity.parent_iter(&cx.tree).find(|ancestor| is_focusable(cx, *ancestor))
+    {
+        set_focus(cx, focusable);
+    }
+}
+
+/// Moves keyboard focus to the next entity in the focus order, wrapping around at the end.
+pub fn focus_next(cx: &mut Context) {
+    let next = cx.style.focus_order.get(cx.focused).map(|focus_order| focus_order.next);
+
+    if let Some(next) = next.filter(|next| is_tabbable(cx, *next)) {
+        set_focus(cx, next);
+        return;
+    }
+
+    let order = tab_order(cx, focus_scope(cx, cx.focused));
+
+    let next = match order.iter().position(|entity| *entity == cx.focused) {
+        Some(index) => order.get((index + 1) % order.len()),
+        None => order.first(),
+    };
+
+    if let Some(next) = next.copied() {
+        set_focus(cx, next);
+    }
+}
+
+/// Moves keyboard focus to the previous entity in the focus order, wrapping around at the start.
+pub fn focus_prev(cx: &mut Context) {
+    let prev = cx.style.focus_order.get(cx.focused).map(|focus_order| focus_order.prev);
+
+    if let Some(prev) = prev.filter(|prev| is_tabbable(cx, *prev)) {
+        set_focus(cx, prev);
+        return;
+    }
+
+    let order = tab_order(cx, focus_scope(cx, cx.focused));
+
+    let prev = match order.iter().position(|entity| *entity == cx.focused) {
+        Some(index) => order.get((index + order.len() - 1) % order.len()),
+        None => order.last(),
+    };
+
+    if let Some(prev) = prev.copied() {
+        set_focus(cx, prev);
+    }
+}
+
+/// Returns the entities within a scope which can be focused with the Tab key, in focus order.
+///
+/// Entities with a positive tab index come first, in ascending order, followed by entities with
+/// a tab index of zero, or no tab index, in tree order.
+pub fn tab_order(cx: &Context, scope: Entity) -> Vec<Entity> {
+    let mut order =
+        scope.branch_iter(&cx.tree).filter(|entity| is_tabbable(cx, *entity)).collect::<Vec<_>>();
+
+    // Stable sort, so entities with the same tab index stay in tree order
+    order.sort_by_key(|entity| {
+        let tab_index = cx.style.tab_index.get(*entity).copied().unwrap_or_default();
+        (tab_index == 0, tab_index)
+    });
+
+    order
+}
+
+// Returns the nearest focus scope which contains the entity, or the root if there is none
+fn focus_scope(cx: &Context, entity: Entity) -> Entity {
+    entity
+        .parent_iter(&cx.tree)
+        .find(|ancestor| cx.style.focus_scope.get(*ancestor).copied().unwrap_or_default())
+        .unwrap_or(Entity::root())
+}
+
+// Returns true if the entity can be focused with the Tab key
+fn is_tabbable(cx: &Context, entity: Entity) -> bool {
+    let tab_index = cx.style.tab_index.get(entity).copied().unwrap_or_default();
+
+    tab_index >= 0 && is_focusable(cx, entity)
+}
+
+// Returns true if the entity is focusable, enabled and visible
+fn is_focusable(cx: &Context, entity: Entity) -> bool {
+    if entity == Entity::null() {
+        return false;
+    }
+
+    let focusable = accepts_focus(cx, entity);
+
+    let disabled = cx.style.disabled.get(entity).copied().unwrap_or_default();
+
+    // Hidden entities, or entities with a hidden ancestor, cannot be focused
+    let hidden = entity.parent_iter(&cx.tree).any(|ancestor| {
+        cx.cache.get_visibility(ancestor) == Visibility::Invisible
+            || cx.cache.get_display(ancestor) == Display::None
+    });
+
+    focusable && !disabled && !hidden
+}
+
+/// Returns true if the entity has opted in to keyboard focus, with `Handle::focusable` or
+/// `Handle::tab_index`.
+///
+/// Every entity has the `FOCUSABLE` ability by default, so it is the tab index which records that
+/// the entity opted in.
+pub fn accepts_focus(cx: &Context, entity: Entity) -> bool {
+    cx.style.tab_index.get(entity).is_some()
+        && cx
+            .style
+            .abilities
+            .get(entity)
+            .map_or(false, |abilities| abilities.contains(Abilities::FOCUSABLE))
+}
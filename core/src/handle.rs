@@ -1,10 +1,12 @@
+use std::any::TypeId;
 use std::marker::PhantomData;
+use std::path::PathBuf;
 
 use morphorm::{LayoutType, PositionType, Units};
 
 use crate::{
-    style::Overflow, Abilities, BorderCornerShape, Color, Context, CursorIcon, Display, Entity,
-    PseudoClass, Res, Visibility,
+    drop_fn, style::Overflow, Abilities, BorderCornerShape, Color, Context, CursorIcon, Display,
+    Entity, PseudoClass, Res, Visibility,
 };
 
 macro_rules! set_style {
@@ -50,6 +52,17 @@ impl<'a, T> Handle<'a, T> {
         self
     }
 
+    /// Sets the id of the view, which can be used to refer to the view in css selectors, e.g. `#save`.
+    pub fn id(self, id: &str) -> Self {
+        self.cx.style.ids.insert(self.entity, id.to_owned()).expect("Failed to insert id");
+
+        self.cx.style.needs_restyle = true;
+        self.cx.style.needs_relayout = true;
+        self.cx.style.needs_redraw = true;
+
+        self
+    }
+
     pub fn font(self, font_name: &str) -> Self {
         self.cx.style.font.insert(self.entity, font_name.to_owned());
 
@@ -73,7 +86,8 @@ impl<'a, T> Handle<'a, T> {
         self
     }
 
-    pub fn disabled(self, state: bool) -> Self {
+    pub fn disabled(self, state: impl Res<bool>) -> Self {
+        let state = *state.get(self.cx);
         self.cx.style.disabled.insert(self.entity, state);
         self.cx.style.needs_restyle = true;
 
@@ -138,6 +152,141 @@ impl<'a, T> Handle<'a, T> {
         self
     }
 
+    /// Sets whether the view can receive keyboard focus, either by clicking or with the Tab key.
+    ///
+    /// Views only take focus once they opt in, either with this method or [Handle::tab_index]. A
+    /// view which opts in with this method is focused by Tab in tree order.
+    pub fn focusable(self, state: bool) -> Self {
+        if let Some(abilities) = self.cx.style.abilities.get_mut(self.entity) {
+            abilities.set(Abilities::FOCUSABLE, state);
+        }
+
+        if state && self.cx.style.tab_index.get(self.entity).is_none() {
+            self.cx.style.tab_index.insert(self.entity, 0).expect("Failed to set tab index");
+        }
+
+        self
+    }
+
+    /// Sets the position of the view in the Tab focus order, and makes the view focusable.
+    ///
+    /// Views with a positive tab index are focused first, in ascending order, followed by views
+    /// with a tab index of zero in tree order. Views with a negative tab index are skipped by Tab
+    /// but can still be focused by clicking.
+    pub fn tab_index(self, index: i32) -> Self {
+        self.cx.style.tab_index.insert(self.entity, index).expect("Failed to set tab index");
+
+        self.focusable(true)
+    }
+
+    /// Sets whether the view is a focus scope.
+    ///
+    /// While focus is inside a focus scope, such as a modal dialog, Tab only moves focus between
+    /// the focusable descendants of the scope.
+    pub fn focus_scope(self, state: bool) -> Self {
+        self.cx.style.focus_scope.insert(self.entity, state).expect("Failed to set focus scope");
+
+        self
+    }
+
+    /// Makes the view a scope for the key bindings with the given scope name.
+    ///
+    /// Bindings in the scope only apply while the focused view is the view or one of its descendants.
+    pub fn keymap_scope(self, scope: &str) -> Self {
+        self.cx.keymap.set_scope(self.entity, scope);
+
+        self
+    }
+
+    /// Makes the view draggable, with a function which creates the payload of a drag operation.
+    ///
+    /// A drag operation starts when the left mouse button is pressed on the view, or one of its
+    /// descendants, and the cursor moves further than the drag threshold. Releasing the button
+    /// over a view which accepts the type of the payload with [Handle::on_drop] drops the payload
+    /// onto it, while Escape cancels the drag operation.
+    pub fn draggable<P, F>(self, payload: F) -> Self
+    where
+        P: 'static,
+        F: 'static + Fn(&Context) -> P,
+    {
+        self.cx.drag.add_source(self.entity, Box::new(move |cx| Box::new((payload)(cx))));
+
+        self
+    }
+
+    /// Sets the content of the preview which follows the cursor while the view is being dragged.
+    ///
+    /// The view must be made draggable with [Handle::draggable] first.
+    pub fn drag_preview<F>(self, content: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        self.cx.drag.set_preview(self.entity, Box::new(content));
+
+        self
+    }
+
+    /// Calls the callback with the payload when a payload of type `P` is dropped onto the view.
+    ///
+    /// While a payload of type `P` is dragged over the view, the view has the `:drag-over`
+    /// pseudo-class and receives `DragEvent`s.
+    pub fn on_drop<P, F>(self, callback: F) -> Self
+    where
+        P: 'static,
+        F: 'static + Fn(&mut Context, P),
+    {
+        self.cx.drag.add_target(self.entity, TypeId::of::<P>(), drop_fn(callback));
+
+        self
+    }
+
+    /// Calls the callback with the path of each file which is dropped onto the view from another
+    /// application.
+    ///
+    /// While files are dragged over the view, the view has the `:drag-over` pseudo-class.
+    pub fn on_file_drop<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut Context, PathBuf),
+    {
+        self.cx.drag.add_file_target(self.entity, Box::new(callback));
+
+        self
+    }
+
+    /// Sets the text of the tooltip which is shown after the cursor rests on the view.
+    pub fn tooltip<U: ToString>(self, text: impl Res<U>) -> Self {
+        let text = text.get(self.cx).to_string();
+        self.cx.style.tooltip.insert(self.entity, text).expect("Failed to set tooltip");
+
+        self
+    }
+
+    /// Sets the content of the tooltip which is shown after the cursor rests on the view.
+    ///
+    /// The content is built each time the tooltip is shown, so it can show the current state of
+    /// the view, such as the name and value of a parameter.
+    pub fn tooltip_content<F>(self, content: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        self.cx.tooltip.set_content(self.entity, Box::new(content));
+
+        self
+    }
+
+    /// Sets the content of the menu which opens at the cursor when the view is right-clicked.
+    ///
+    /// The content is built each time the menu opens, typically from `MenuItem`, `Submenu` and
+    /// `MenuSeparator` views.
+    pub fn context_menu<F>(self, content: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        self.cx.menus.set_context_menu(self.entity, Box::new(content));
+
+        self
+    }
+
     pub fn child_space(self, value: Units) -> Self {
         self.cx.style.child_left.insert(self.entity, value);
         self.cx.style.child_right.insert(self.entity, value);
@@ -0,0 +1,534 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use crate::{Code, Context, Entity, Event, Message, Modifiers, Propagation, Tree, TreeExt};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapError {
+    // A key in a chord could not be parsed
+    InvalidKey(String),
+    // A key sequence contains no chords
+    EmptySequence,
+    // A line of a keymap is not a scope header or an `action = keys` binding
+    InvalidLine(usize),
+}
+
+impl fmt::Display for KeymapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeymapError::InvalidKey(key) => write!(f, "invalid key: {}", key),
+            KeymapError::EmptySequence => write!(f, "empty key sequence"),
+            KeymapError::InvalidLine(line) => write!(f, "invalid keymap line: {}", line),
+        }
+    }
+}
+
+impl std::error::Error for KeymapError {}
+
+/// A key pressed together with a set of modifiers, such as `Ctrl+Shift+S`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyChord {
+    pub modifiers: Modifiers,
+    pub code: Code,
+}
+
+impl KeyChord {
+    pub fn new(modifiers: Modifiers, code: Code) -> Self {
+        Self { modifiers, code }
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (modifier, name) in [
+            (Modifiers::CTRL, "Ctrl"),
+            (Modifiers::ALT, "Alt"),
+            (Modifiers::SHIFT, "Shift"),
+            (Modifiers::LOGO, "Logo"),
+        ] {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{}+", name)?;
+            }
+        }
+
+        // Letters and digits are written without their `Key` and `Digit` prefixes
+        let code = format!("{:?}", self.code);
+        match code.strip_prefix("Key").or_else(|| code.strip_prefix("Digit")) {
+            Some(short) if short.len() == 1 => write!(f, "{}", short),
+            _ => write!(f, "{}", code),
+        }
+    }
+}
+
+impl FromStr for KeyChord {
+    type Err = KeymapError;
+
+    /// Parses a chord such as `Ctrl+Shift+S`, `Alt+F4` or `Shift+ArrowUp`.
+    ///
+    /// Modifier names are case-insensitive. Keys are named after their [Code], where letters and
+    /// digits may be written without their `Key` and `Digit` prefixes.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('+').map(str::trim).collect::<Vec<_>>();
+
+        let key = parts.pop().filter(|key| !key.is_empty());
+        let key = key.ok_or_else(|| KeymapError::InvalidKey(s.to_owned()))?;
+
+        let mut modifiers = Modifiers::empty();
+        for part in parts {
+            modifiers |= match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => Modifiers::CTRL,
+                "alt" | "option" => Modifiers::ALT,
+                "shift" => Modifiers::SHIFT,
+                "logo" | "meta" | "super" | "cmd" => Modifiers::LOGO,
+                _ => return Err(KeymapError::InvalidKey(part.to_owned())),
+            };
+        }
+
+        let code = if key.len() == 1 && key.chars().all(|c| c.is_ascii_alphabetic()) {
+            format!("Key{}", key.to_ascii_uppercase())
+        } else if key.len() == 1 && key.chars().all(|c| c.is_ascii_digit()) {
+            format!("Digit{}", key)
+        } else {
+            key.to_owned()
+        };
+
+        let code = code.parse::<Code>().map_err(|_| KeymapError::InvalidKey(key.to_owned()))?;
+
+        Ok(Self { modifiers, code })
+    }
+}
+
+/// A sequence of chords which are pressed one after another, such as `Ctrl+K Ctrl+C`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeySequence(pub Vec<KeyChord>);
+
+impl KeySequence {
+    // Returns true if the chords are the start of this sequence
+    fn starts_with(&self, chords: &[KeyChord]) -> bool {
+        self.0.starts_with(chords)
+    }
+}
+
+impl From<KeyChord> for KeySequence {
+    fn from(chord: KeyChord) -> Self {
+        Self(vec![chord])
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, chord) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{}", chord)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for KeySequence {
+    type Err = KeymapError;
+
+    /// Parses a sequence of chords separated by whitespace, such as `Ctrl+K Ctrl+C`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chords = s.split_whitespace().map(str::parse).collect::<Result<Vec<_>, _>>()?;
+
+        if chords.is_empty() {
+            return Err(KeymapError::EmptySequence);
+        }
+
+        Ok(Self(chords))
+    }
+}
+
+/// A key binding, which triggers an action when its key sequence is pressed within its scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBinding {
+    /// The name of the scope of the binding, or None for an app-wide binding.
+    pub scope: Option<String>,
+    pub keys: KeySequence,
+    pub action: String,
+}
+
+// An action which is triggered by a key binding
+enum Action {
+    // Creates the event which is sent to the focused entity
+    Message(Box<dyn Fn() -> Event>),
+    // Is called with the focused entity as the current entity, and is removed along with the
+    // entity which owns it, if there is one
+    Callback(Option<Entity>, Rc<dyn Fn(&mut Context)>),
+}
+
+// The result of resolving a pressed chord against the bindings
+pub(crate) enum KeymapResult {
+    // The chord completed a binding, which produced this event
+    Action(Event),
+    // The chord completed a binding, which calls this callback
+    Callback(Rc<dyn Fn(&mut Context)>),
+    // The chord is the start of a longer binding, so the next chord is awaited
+    Pending,
+    // The chord does not match any binding
+    None,
+}
+
+/// Storage for key bindings and the actions they trigger.
+///
+/// Bindings map a key sequence to the name of an action, within a named scope or app-wide.
+/// Entities are made into scopes with `Handle::keymap_scope`, and a binding in a scope only
+/// applies while the focused entity is within one of the entities of that scope. When a key is
+/// pressed, the scopes of the ancestors of the focused entity are searched from the innermost
+/// outwards, followed by the app-wide bindings.
+///
+/// Actions are registered with [Keymap::add_action], and their message is sent to the focused
+/// entity with [Propagation::Up] when one of their bindings is pressed. Actions registered with
+/// [Keymap::add_callback] are instead called with the focused entity as the current entity.
+/// Since bindings only refer to actions by name, they can be changed at runtime and saved and
+/// loaded as text with the `Display` and `FromStr` implementations, in the following format:
+///
+/// ```text
+/// # App-wide bindings
+/// save = Ctrl+S
+/// save_as = Ctrl+Shift+S
+///
+/// [editor]
+/// comment = Ctrl+K Ctrl+C
+/// ```
+#[derive(Default)]
+pub struct Keymap {
+    bindings: Vec<KeyBinding>,
+    actions: HashMap<String, Action>,
+    scopes: HashMap<Entity, String>,
+    // The chords which have been pressed so far of a binding with several chords
+    pending: Vec<KeyChord>,
+}
+
+impl Keymap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an action which sends a message when one of its bindings is pressed.
+    pub fn add_action<M: Message + Clone>(&mut self, name: &str, message: M) {
+        let action = Action::Message(Box::new(move || Event::new(message.clone())));
+        self.actions.insert(name.to_owned(), action);
+    }
+
+    /// Registers an action which calls a callback when one of its bindings is pressed.
+    ///
+    /// The callback is called with the focused entity as the current entity, so any events it
+    /// emits are sent from the focused entity.
+    pub fn add_callback<F>(&mut self, name: &str, callback: F)
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        self.actions.insert(name.to_owned(), Action::Callback(None, Rc::new(callback)));
+    }
+
+    /// Registers a callback action which belongs to an entity, such as a menu item.
+    ///
+    /// The action is removed when the entity is removed, unless it has since been replaced.
+    pub fn add_entity_callback<F>(&mut self, entity: Entity, name: &str, callback: F)
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        self.actions.insert(name.to_owned(), Action::Callback(Some(entity), Rc::new(callback)));
+    }
+
+    pub fn remove_action(&mut self, name: &str) {
+        self.actions.remove(name);
+    }
+
+    /// Binds a key sequence to an action, within a named scope or app-wide if the scope is None.
+    ///
+    /// Any existing binding of the same key sequence in the same scope is replaced.
+    pub fn bind(
+        &mut self,
+        scope: Option<&str>,
+        keys: &str,
+        action: &str,
+    ) -> Result<(), KeymapError> {
+        let keys = keys.parse::<KeySequence>()?;
+        let scope = scope.map(str::to_owned);
+
+        self.bindings.retain(|binding| binding.scope != scope || binding.keys != keys);
+        self.bindings.push(KeyBinding { scope, keys, action: action.to_owned() });
+
+        Ok(())
+    }
+
+    /// Removes the binding of a key sequence within a scope, returning true if it existed.
+    pub fn unbind(&mut self, scope: Option<&str>, keys: &str) -> Result<bool, KeymapError> {
+        let keys = keys.parse::<KeySequence>()?;
+
+        let len = self.bindings.len();
+        self.bindings.retain(|binding| binding.scope.as_deref() != scope || binding.keys != keys);
+        Ok(self.bindings.len() != len)
+    }
+
+    /// Replaces all of the bindings of an action within a scope with a single key sequence.
+    pub fn rebind(
+        &mut self,
+        scope: Option<&str>,
+        action: &str,
+        keys: &str,
+    ) -> Result<(), KeymapError> {
+        let keys = keys.parse::<KeySequence>()?;
+
+        self.bindings
+            .retain(|binding| binding.scope.as_deref() != scope || binding.action != action);
+
+        self.bind(scope, &keys.to_string(), action)
+    }
+
+    /// Replaces all of the bindings with the bindings of another keymap, such as one loaded from
+    /// text.
+    ///
+    /// Registered actions and scopes are kept.
+    pub fn set_bindings(&mut self, other: Keymap) {
+        self.bindings = other.bindings;
+        self.pending.clear();
+    }
+
+    pub fn bindings(&self) -> &[KeyBinding] {
+        &self.bindings
+    }
+
+    /// Returns the key sequence of the first binding of an action, preferring app-wide bindings.
+    pub fn keys_for(&self, action: &str) -> Option<&KeySequence> {
+        let mut bindings = self.bindings.iter().filter(|binding| binding.action == action);
+
+        bindings
+            .clone()
+            .find(|binding| binding.scope.is_none())
+            .or_else(|| bindings.next())
+            .map(|binding| &binding.keys)
+    }
+
+    /// Makes an entity a scope for the bindings with the given scope name.
+    pub fn set_scope(&mut self, entity: Entity, scope: &str) {
+        self.scopes.insert(entity, scope.to_owned());
+    }
+
+    /// Removes the scope and the callback actions of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.scopes.remove(&entity);
+
+        self.actions.retain(|_, action| match action {
+            Action::Callback(owner, _) => *owner != Some(entity),
+            Action::Message(_) => true,
+        });
+    }
+
+    // Resolves a pressed chord against the bindings of the scopes which contain the focused entity
+    pub(crate) fn resolve(
+        &mut self,
+        tree: &Tree,
+        focused: Entity,
+        chord: KeyChord,
+    ) -> KeymapResult {
+        self.pending.push(chord);
+
+        match self.find(tree, focused) {
+            KeymapResult::None if self.pending.len() > 1 => {
+                // The chord did not continue the pending sequence, so try it as the start of a new
+                // one
+                self.pending = vec![chord];
+                self.find(tree, focused)
+            }
+
+            result => result,
+        }
+    }
+
+    fn find(&mut self, tree: &Tree, focused: Entity) -> KeymapResult {
+        let scopes = focused
+            .parent_iter(tree)
+            .filter_map(|entity| self.scopes.get(&entity).map(|scope| Some(scope.as_str())))
+            .chain(std::iter::once(None))
+            .collect::<Vec<_>>();
+
+        for scope in scopes {
+            let mut exact = None;
+            let mut longer = false;
+
+            for binding in self.bindings.iter().filter(|binding| {
+                binding.scope.as_deref() == scope && binding.keys.starts_with(&self.pending)
+            }) {
+                if binding.keys.0.len() == self.pending.len() {
+                    exact = Some(binding.action.as_str());
+                } else {
+                    longer = true;
+                }
+            }
+
+            // Wait for the next chord if a longer binding could still match
+            if longer {
+                return KeymapResult::Pending;
+            }
+
+            if let Some(action) = exact {
+                let result = match self.actions.get(action) {
+                    Some(Action::Message(event)) => KeymapResult::Action(
+                        (event)().target(focused).origin(focused).propagate(Propagation::Up),
+                    ),
+                    Some(Action::Callback(_, callback)) => KeymapResult::Callback(callback.clone()),
+                    None => KeymapResult::None,
+                };
+
+                self.pending.clear();
+
+                return result;
+            }
+        }
+
+        self.pending.clear();
+
+        KeymapResult::None
+    }
+}
+
+impl fmt::Display for Keymap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut scopes = vec![None];
+        for binding in self.bindings.iter() {
+            if !scopes.contains(&binding.scope.as_deref()) {
+                scopes.push(binding.scope.as_deref());
+            }
+        }
+
+        for scope in scopes {
+            if let Some(scope) = scope {
+                writeln!(f, "\n[{}]", scope)?;
+            }
+
+            for binding in self.bindings.iter().filter(|binding| binding.scope.as_deref() == scope)
+            {
+                writeln!(f, "{} = {}", binding.action, binding.keys)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Keymap {
+    type Err = KeymapError;
+
+    /// Parses bindings from text, with one `action = keys` binding per line.
+    ///
+    /// Bindings are app-wide until a `[scope]` header, after which they belong to that scope.
+    /// Empty lines and lines starting with `#` are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut keymap = Keymap::new();
+        let mut scope = None;
+
+        for (index, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|line| line.strip_suffix(']')) {
+                scope = Some(name.trim().to_owned());
+                continue;
+            }
+
+            let (action, keys) = line.split_once('=').ok_or(KeymapError::InvalidLine(index + 1))?;
+
+            keymap.bind(scope.as_deref(), keys.trim(), action.trim())?;
+        }
+
+        Ok(keymap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Action {
+        Save,
+        Comment,
+    }
+
+    #[test]
+    fn parse_and_display_keymap() {
+        let chord = "ctrl+shift+s".parse::<KeyChord>().unwrap();
+        assert_eq!(chord, KeyChord::new(Modifiers::CTRL | Modifiers::SHIFT, Code::KeyS));
+        assert_eq!(chord.to_string(), "Ctrl+Shift+S");
+
+        let text = "save = Ctrl+S\n\n[editor]\ncomment = Ctrl+K Ctrl+C\n";
+        let keymap = text.parse::<Keymap>().unwrap();
+        assert_eq!(keymap.to_string(), text);
+
+        assert_eq!("save".parse::<Keymap>().err(), Some(KeymapError::InvalidLine(1)));
+    }
+
+    #[test]
+    fn chords_resolve_in_scope() {
+        let mut tree = Tree::new();
+        let editor = Entity::new(1, 0);
+        let textbox = Entity::new(2, 0);
+        tree.add(editor, Entity::root()).unwrap();
+        tree.add(textbox, editor).unwrap();
+
+        let mut keymap = Keymap::new();
+        keymap.add_action("save", Action::Save);
+        keymap.add_action("comment", Action::Comment);
+        keymap.bind(None, "Ctrl+S", "save").unwrap();
+        keymap.bind(Some("editor"), "Ctrl+K Ctrl+C", "comment").unwrap();
+
+        let ctrl = |code| KeyChord::new(Modifiers::CTRL, code);
+
+        // The scoped binding does not apply outside of its scope
+        assert!(matches!(
+            keymap.resolve(&tree, Entity::root(), ctrl(Code::KeyK)),
+            KeymapResult::None
+        ));
+
+        keymap.set_scope(editor, "editor");
+
+        assert!(matches!(keymap.resolve(&tree, textbox, ctrl(Code::KeyK)), KeymapResult::Pending));
+        match keymap.resolve(&tree, textbox, ctrl(Code::KeyC)) {
+            KeymapResult::Action(mut event) => {
+                assert_eq!(event.message.downcast::<Action>(), Some(&mut Action::Comment));
+                assert_eq!(event.target, textbox);
+            }
+            _ => panic!("Expected the comment action"),
+        }
+
+        // App-wide bindings apply everywhere
+        assert!(matches!(
+            keymap.resolve(&tree, textbox, ctrl(Code::KeyS)),
+            KeymapResult::Action(_)
+        ));
+    }
+
+    #[test]
+    fn entity_callbacks_are_removed_with_their_entity() {
+        let tree = Tree::new();
+        let item = Entity::new(1, 0);
+        let rebuilt = Entity::new(2, 0);
+        let chord = KeyChord::new(Modifiers::CTRL, Code::KeyS);
+
+        let mut keymap = Keymap::new();
+        keymap.bind(None, "Ctrl+S", "save").unwrap();
+
+        keymap.add_entity_callback(item, "save", |_| {});
+        keymap.remove_entity(item);
+        assert!(matches!(keymap.resolve(&tree, Entity::root(), chord), KeymapResult::None));
+
+        // Removing an entity does not remove a callback which has replaced its own
+        keymap.add_entity_callback(item, "save", |_| {});
+        keymap.add_entity_callback(rebuilt, "save", |_| {});
+        keymap.remove_entity(item);
+        assert!(matches!(keymap.resolve(&tree, Entity::root(), chord), KeymapResult::Callback(_)));
+    }
+}
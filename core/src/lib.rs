@@ -62,6 +62,21 @@ pub use state::*;
 mod hover_system;
 pub use hover_system::apply_hover;
 
+mod focus_system;
+pub use focus_system::*;
+
+mod drag_system;
+pub use drag_system::*;
+
+mod tooltip_system;
+pub use tooltip_system::*;
+
+mod menu_system;
+pub use menu_system::*;
+
+mod portal_system;
+pub use portal_system::*;
+
 mod style_system;
 pub use style_system::*;
 
@@ -78,4 +93,24 @@ pub use extention::*;
 mod enviroment;
 pub use enviroment::*;
 
+mod runtime;
+pub use runtime::Runtime;
+
+mod timer;
+pub use timer::*;
+
+mod proxy;
+pub use proxy::*;
+
+mod task;
+pub use task::{TaskHandle, Tasks};
+
+mod keymap;
+pub use keymap::*;
+
+#[cfg(feature = "software")]
+pub mod software;
+#[cfg(feature = "software")]
+pub use software::SoftwareCanvas;
+
 pub use keyboard_types::{Code, Key};
@@ -0,0 +1,243 @@
+use std::collections::{HashMap, HashSet};
+
+use morphorm::Units;
+
+use crate::{
+    accepts_focus, set_focus, Context, Display, Entity, MenuPopup, MouseButton, PropSet, TreeExt,
+};
+
+/// Storage for the context menus of all entities, and the menus which are open.
+///
+/// Menus are opened as a stack, starting with a context menu or a menu of a menu bar, followed by
+/// any open submenus. Keyboard focus moves into a menu when it opens, and returns to the
+/// previously focused entity when all menus are closed.
+pub struct MenuState {
+    builders: HashMap<Entity, Box<dyn Fn(&mut Context)>>,
+    open: Vec<Entity>,
+    // Menus which are hidden when closed instead of being removed, such as those of a menu bar
+    persistent: HashSet<Entity>,
+    // Labels which show the keys bound to an action, updated whenever their menu is shown
+    shortcut_labels: HashMap<Entity, String>,
+    // The entity which was focused before the first menu was opened
+    prev_focused: Entity,
+}
+
+impl Default for MenuState {
+    fn default() -> Self {
+        Self {
+            builders: HashMap::new(),
+            open: Vec::new(),
+            persistent: HashSet::new(),
+            shortcut_labels: HashMap::new(),
+            prev_focused: Entity::null(),
+        }
+    }
+}
+
+impl MenuState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Sets the content of the context menu of an entity
+    pub(crate) fn set_context_menu(&mut self, entity: Entity, content: Box<dyn Fn(&mut Context)>) {
+        self.builders.insert(entity, content);
+    }
+
+    // Makes a menu hidden instead of removed when it is closed
+    pub(crate) fn set_persistent(&mut self, menu: Entity) {
+        self.persistent.insert(menu);
+    }
+
+    // Makes a label show the keys bound to an action
+    pub(crate) fn set_shortcut_label(&mut self, label: Entity, action: &str) {
+        self.shortcut_labels.insert(label, action.to_owned());
+    }
+
+    /// Removes the context menu of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.builders.remove(&entity);
+        self.open.retain(|menu| *menu != entity);
+        self.persistent.remove(&entity);
+        self.shortcut_labels.remove(&entity);
+    }
+
+    // Returns true if the entity is a label which shows the keys bound to an action
+    pub(crate) fn is_shortcut_label(&self, entity: Entity) -> bool {
+        self.shortcut_labels.contains_key(&entity)
+    }
+
+    /// Returns true if the menu is hidden instead of removed when it is closed.
+    pub fn is_persistent(&self, menu: Entity) -> bool {
+        self.persistent.contains(&menu)
+    }
+
+    /// Returns true if any menu is open.
+    pub fn is_open(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    /// Returns the open menus, from the first menu which was opened to the innermost submenu.
+    pub fn open_menus(&self) -> &[Entity] {
+        &self.open
+    }
+}
+
+/// Opens a menu as a child of the parent entity, at the given position relative to the parent.
+///
+/// Any open menus which do not contain the parent are closed first, and keyboard focus moves to
+/// the first enabled item of the new menu. Returns the entity of the menu.
+pub fn open_menu(
+    cx: &mut Context,
+    parent: Entity,
+    left: f32,
+    top: f32,
+    content: &dyn Fn(&mut Context),
+) -> Entity {
+    close_menus_outside(cx, parent);
+
+    let menu = cx.with_current(parent, |cx| MenuPopup::new(cx, |_| {}).entity);
+
+    cx.with_current(menu, |cx| (content)(cx));
+
+    show_menu(cx, menu, left, top);
+
+    menu
+}
+
+/// Shows a menu which is hidden, at the given position relative to its parent.
+///
+/// This is used for persistent menus, such as those of a menu bar, which are built once and
+/// hidden when they are closed. Keyboard focus moves to the first enabled item of the menu.
+pub fn show_menu(cx: &mut Context, menu: Entity, left: f32, top: f32) {
+    if let Some(parent) = cx.tree.get_parent(menu) {
+        close_menus_outside(cx, parent);
+    }
+
+    if cx.menus.open.is_empty() {
+        cx.menus.prev_focused = cx.focused;
+    }
+
+    menu.set_left(cx, Units::Pixels(left)).set_top(cx, Units::Pixels(top));
+    menu.set_display(cx, Display::Flex);
+
+    // Show the keys which are currently bound to the actions of the items
+    let labels = menu
+        .branch_iter(&cx.tree)
+        .filter_map(|entity| Some((entity, cx.menus.shortcut_labels.get(&entity)?.clone())))
+        .collect::<Vec<_>>();
+
+    for (label, action) in labels {
+        let keys = cx.keymap.keys_for(&action).map(|keys| keys.to_string()).unwrap_or_default();
+        label.set_text(cx, &keys);
+    }
+
+    cx.menus.open.push(menu);
+
+    // The items of a menu which was hidden are not focusable until the next layout
+    let first = menu.branch_iter(&cx.tree).skip(1).find(|entity| {
+        accepts_focus(cx, *entity) && !cx.style.disabled.get(*entity).copied().unwrap_or_default()
+    });
+
+    set_focus(cx, first.unwrap_or(menu));
+}
+
+/// Returns the open menu which was opened as a child of an entity, if there is one.
+pub fn menu_of(cx: &Context, parent: Entity) -> Option<Entity> {
+    cx.menus.open.iter().copied().find(|menu| cx.tree.get_parent(*menu) == Some(parent))
+}
+
+/// Closes a menu and any submenus which were opened from it.
+///
+/// Keyboard focus moves to the entity which opened the menu if other menus are still open, or
+/// returns to the entity which was focused before the first menu was opened.
+pub fn close_menu(cx: &mut Context, menu: Entity) {
+    let index = match cx.menus.open.iter().position(|open| *open == menu) {
+        Some(index) => index,
+        None => return,
+    };
+
+    let opener = cx.tree.get_parent(menu);
+
+    let closed = cx.menus.open.split_off(index);
+    for menu in closed.iter().rev() {
+        if cx.menus.persistent.contains(menu) {
+            menu.set_display(cx, Display::None);
+        } else {
+            cx.remove(*menu);
+        }
+    }
+
+    if cx.menus.open.is_empty() {
+        let prev_focused = cx.menus.prev_focused;
+        set_focus(
+            cx,
+            if cx.entity_manager.is_alive(prev_focused) { prev_focused } else { Entity::root() },
+        );
+    } else if let Some(opener) = opener {
+        set_focus(cx, opener);
+    }
+}
+
+/// Closes all open menus.
+pub fn close_all_menus(cx: &mut Context) {
+    if let Some(first) = cx.menus.open.first().copied() {
+        close_menu(cx, first);
+    }
+}
+
+/// Closes the open menus which do not contain the entity, such as the submenus of a sibling item.
+pub fn close_menus_outside(cx: &mut Context, entity: Entity) {
+    let outside = cx
+        .menus
+        .open
+        .iter()
+        .copied()
+        .find(|menu| !entity.parent_iter(&cx.tree).any(|ancestor| ancestor == *menu));
+
+    if let Some(menu) = outside {
+        close_menu(cx, menu);
+    }
+}
+
+/// Handles a mouse button being pressed.
+///
+/// Pressing outside of the open menus closes them. Pressing the right mouse button opens the
+/// context menu of the nearest view under the cursor which has one.
+pub fn menu_press(cx: &mut Context, button: MouseButton) {
+    let hovered = cx.hovered;
+
+    // Pressing the parent of a persistent menu, such as the title of a menu in a menu bar, is
+    // left to the parent, which toggles the menu
+    let outside = cx.menus.open.iter().copied().find(|menu| {
+        let contains = |entity: Entity| hovered.parent_iter(&cx.tree).any(|e| e == entity);
+        let parent = cx.tree.get_parent(*menu).unwrap_or(Entity::null());
+
+        !contains(*menu) && !(cx.menus.persistent.contains(menu) && contains(parent))
+    });
+
+    if let Some(menu) = outside {
+        close_menu(cx, menu);
+    }
+
+    if button != MouseButton::Right || cx.menus.is_open() {
+        return;
+    }
+
+    let owner = match hovered.parent_iter(&cx.tree).find(|e| cx.menus.builders.contains_key(e)) {
+        Some(owner) => owner,
+        None => return,
+    };
+
+    // Take the content out of the storage so that it can be built with a mutable context
+    if let Some(content) = cx.menus.builders.remove(&owner) {
+        let left = cx.mouse.cursorx - cx.cache.get_posx(owner);
+        let top = cx.mouse.cursory - cx.cache.get_posy(owner);
+
+        open_menu(cx, owner, left, top, &*content);
+
+        if cx.entity_manager.is_alive(owner) {
+            cx.menus.builders.insert(owner, content);
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::HashMap;
+
+use morphorm::Units;
+
+use crate::{BoundingBox, Context, Display, Entity, PropSet, Visibility};
+
+/// Where a portal or popup is placed relative to its anchor.
+///
+/// If the content would overflow the window in the preferred direction and fits on the opposite
+/// side, it is flipped to that side. It is then shifted to stay inside the window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    /// Below the anchor, aligned with its left edge.
+    Below,
+    /// Above the anchor, aligned with its left edge.
+    Above,
+    /// To the left of the anchor, aligned with its top edge.
+    Left,
+    /// To the right of the anchor, aligned with its top edge.
+    Right,
+    /// At the position of the cursor when the content is shown.
+    Cursor,
+}
+
+impl Default for Placement {
+    fn default() -> Self {
+        Placement::Below
+    }
+}
+
+/// Storage for the anchors of all portals.
+///
+/// A portal is an overlay which is laid out in window coordinates next to its anchor, and which is
+/// not clipped or transformed by the views which contain it. Its content is still part of the
+/// subtree in which it was built, so data lookup and event propagation work as they would for any
+/// other child. See [Portal](crate::Portal).
+#[derive(Default)]
+pub struct PortalState {
+    portals: HashMap<Entity, PortalInfo>,
+}
+
+#[derive(Clone, Copy)]
+struct PortalInfo {
+    anchor: Entity,
+    placement: Placement,
+    // Whether the width of the portal is set to the width of its anchor
+    match_width: bool,
+    // The position of the cursor when the portal was shown, if it is shown
+    shown_at: Option<(f32, f32)>,
+}
+
+impl PortalState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Makes an entity a portal which is shown next to the anchor
+    pub(crate) fn set_anchor(&mut self, portal: Entity, anchor: Entity) {
+        self.portals.insert(
+            portal,
+            PortalInfo { anchor, placement: Placement::Below, match_width: false, shown_at: None },
+        );
+    }
+
+    // Sets where a portal is placed relative to its anchor
+    pub(crate) fn set_placement(&mut self, portal: Entity, placement: Placement) {
+        if let Some(info) = self.portals.get_mut(&portal) {
+            info.placement = placement;
+        }
+    }
+
+    // Sets whether the width of a portal is set to the width of its anchor
+    pub(crate) fn set_match_width(&mut self, portal: Entity, match_width: bool) {
+        if let Some(info) = self.portals.get_mut(&portal) {
+            info.match_width = match_width;
+        }
+    }
+
+    /// Returns true if the entity is a portal.
+    pub fn is_portal(&self, entity: Entity) -> bool {
+        self.portals.contains_key(&entity)
+    }
+
+    /// Returns the anchor of a portal.
+    pub fn anchor(&self, portal: Entity) -> Option<Entity> {
+        self.portals.get(&portal).map(|info| info.anchor)
+    }
+
+    /// Removes an entity if it is a portal.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.portals.remove(&entity);
+    }
+}
+
+/// Positions each portal next to its anchor, inside the bounds of the window.
+///
+/// A portal is placed on the side of its anchor given by its [Placement], or on the opposite side
+/// if it only fits there, and is then shifted to keep it inside the window. This runs after every
+/// layout, so portals follow their anchors when the geometry of an anchor changes or the window is
+/// resized. Returns true if any portal was moved, in which case the layout must be updated.
+pub fn apply_portals(cx: &mut Context) -> bool {
+    let window = cx.cache.get_bounds(Entity::root());
+    let cursor = (cx.mouse.cursorx, cx.mouse.cursory);
+
+    // Remember where the cursor was when a hidden portal is shown
+    let shown = cx.portals.portals.keys().map(|portal| is_shown(cx, *portal)).collect::<Vec<_>>();
+    for (info, shown) in cx.portals.portals.values_mut().zip(shown) {
+        if shown {
+            info.shown_at.get_or_insert(cursor);
+        } else {
+            info.shown_at = None;
+        }
+    }
+
+    let portals =
+        cx.portals.portals.iter().map(|(portal, info)| (*portal, *info)).collect::<Vec<_>>();
+
+    let mut moved = false;
+
+    for (portal, info) in portals {
+        let shown_at = match info.shown_at {
+            Some(shown_at) if cx.entity_manager.is_alive(info.anchor) => shown_at,
+            _ => continue,
+        };
+
+        let anchor = transformed_bounds(cx, info.anchor);
+
+        let width = if info.match_width { anchor.w } else { cx.cache.get_width(portal) };
+        let height = cx.cache.get_height(portal);
+
+        let (mut x, mut y) = place(info.placement, anchor, shown_at, width, height, window);
+
+        // Shift to stay inside the window
+        x = x.min(window.x + window.w - width).max(window.x);
+        y = y.min(window.y + window.h - height).max(window.y);
+
+        // Self-directed views are positioned relative to their parent
+        let parent = cx.tree.get_parent(portal).unwrap_or(Entity::root());
+        let left = Units::Pixels(x - cx.cache.get_posx(parent));
+        let top = Units::Pixels(y - cx.cache.get_posy(parent));
+
+        if cx.style.left.get(portal) != Some(&left) || cx.style.top.get(portal) != Some(&top) {
+            portal.set_left(cx, left).set_top(cx, top);
+            moved = true;
+        }
+
+        if info.match_width && cx.style.width.get(portal) != Some(&Units::Pixels(width)) {
+            portal.set_width(cx, Units::Pixels(width));
+            moved = true;
+        }
+    }
+
+    moved
+}
+
+// Returns the position of content next to the anchor, flipped to the opposite side if it only
+// fits there
+fn place(
+    placement: Placement,
+    anchor: BoundingBox,
+    cursor: (f32, f32),
+    width: f32,
+    height: f32,
+    window: BoundingBox,
+) -> (f32, f32) {
+    let right = window.x + window.w;
+    let bottom = window.y + window.h;
+
+    let below = anchor.y + anchor.h;
+    let above = anchor.y - height;
+    let after = anchor.x + anchor.w;
+    let before = anchor.x - width;
+
+    match placement {
+        Placement::Below if below + height > bottom && above >= window.y => (anchor.x, above),
+        Placement::Below => (anchor.x, below),
+        Placement::Above if above < window.y && below + height <= bottom => (anchor.x, below),
+        Placement::Above => (anchor.x, above),
+        Placement::Right if after + width > right && before >= window.x => (before, anchor.y),
+        Placement::Right => (after, anchor.y),
+        Placement::Left if before < window.x && after + width <= right => (after, anchor.y),
+        Placement::Left => (before, anchor.y),
+        Placement::Cursor => {
+            let (x, y) = cursor;
+            let x = if x + width > right && x - width >= window.x { x - width } else { x };
+            let y = if y + height > bottom && y - height >= window.y { y - height } else { y };
+            (x, y)
+        }
+    }
+}
+
+// Returns true if the portal is displayed and visible
+fn is_shown(cx: &Context, portal: Entity) -> bool {
+    cx.cache.get_display(portal) != Display::None
+        && cx.cache.get_visibility(portal) != Visibility::Invisible
+}
+
+// Returns the bounds of an entity in window coordinates, after its transform is applied
+fn transformed_bounds(cx: &Context, entity: Entity) -> BoundingBox {
+    let bounds = cx.cache.get_bounds(entity);
+    let transform = cx.cache.get_transform(entity);
+
+    let (x, y) = transform.transform_point(bounds.x, bounds.y);
+    let (right, bottom) = transform.transform_point(bounds.x + bounds.w, bounds.y + bounds.h);
+
+    BoundingBox { x, y, w: right - x, h: bottom - y }
+}
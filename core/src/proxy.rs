@@ -0,0 +1,96 @@
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+
+use crate::{Entity, Event, Message, Propagation};
+
+#[derive(Debug, Clone, Copy)]
+pub enum ProxyError {
+    // The context which receives the events has been dropped.
+    Closed,
+}
+
+/// A handle for sending events to a [Context](crate::Context) from any thread.
+///
+/// A proxy is obtained with `Context::get_proxy`, or from the `get_proxy` method of the
+/// application of a backend. Events which are sent through the proxy are added to the event queue
+/// at the start of the next frame. Backends which sleep while there are no OS events provide a
+/// wakeup callback, which is called after every event sent through the proxy.
+///
+/// The baseview backend has no wakeup callback, because a baseview window cannot be woken up from
+/// another thread. Baseview runs the frames of a window at a fixed rate instead, so events sent
+/// through the proxy wait up to one frame before they are handled.
+#[derive(Clone)]
+pub struct ContextProxy {
+    sender: Sender<Event>,
+    wakeup: Option<Arc<dyn Fn() + Send + Sync>>,
+}
+
+impl ContextProxy {
+    /// Creates a proxy along with the receiver for the events sent through it.
+    ///
+    /// This is used by backends which need a proxy before their context is created. The proxy and
+    /// receiver are then passed to the context with `Context::set_proxy`.
+    pub fn channel() -> (Self, Receiver<Event>) {
+        let (sender, receiver) = channel();
+
+        (Self { sender, wakeup: None }, receiver)
+    }
+
+    // Sets the callback which wakes up the backend when an event is sent
+    pub(crate) fn set_wakeup(&mut self, wakeup: Arc<dyn Fn() + Send + Sync>) {
+        self.wakeup = Some(wakeup);
+    }
+
+    /// Sends an event to the context and wakes up the backend.
+    pub fn send_event(&self, event: Event) -> Result<(), ProxyError> {
+        self.sender.send(event).map_err(|_| ProxyError::Closed)?;
+
+        if let Some(wakeup) = &self.wakeup {
+            (wakeup)();
+        }
+
+        Ok(())
+    }
+
+    /// Sends a message directly to the target entity.
+    pub fn emit_to<M: Message>(&self, target: Entity, message: M) -> Result<(), ProxyError> {
+        self.send_event(Event::new(message).target(target).propagate(Propagation::Direct))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    use super::*;
+
+    #[derive(Debug)]
+    struct Ping;
+
+    #[test]
+    fn sending_an_event_calls_the_wakeup() {
+        let (mut proxy, receiver) = ContextProxy::channel();
+
+        let wakeups = Arc::new(AtomicUsize::new(0));
+        let counter = wakeups.clone();
+        proxy.set_wakeup(Arc::new(move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        }));
+
+        thread::spawn(move || proxy.emit_to(Entity::root(), Ping).unwrap()).join().unwrap();
+
+        let mut event = receiver.try_recv().expect("Event was not sent");
+        assert!(event.message.downcast::<Ping>().is_some());
+        assert_eq!(event.target, Entity::root());
+        assert_eq!(wakeups.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn sending_after_the_receiver_is_dropped_fails() {
+        let (proxy, receiver) = ContextProxy::channel();
+        drop(receiver);
+
+        assert!(matches!(proxy.emit_to(Entity::root(), Ping), Err(ProxyError::Closed)));
+    }
+}
@@ -29,6 +29,9 @@ pub struct ResourceManager {
     pub themes: Vec<String>,      // Themes are the string content stylesheets
     //pub images: Vec<Image>,
     pub fonts: HashMap<String, FontOrId>,
+    // Font files are kept for the software renderer, which loads fonts separately
+    #[cfg(feature = "software")]
+    pub font_data: HashMap<String, Vec<u8>>,
 
     //pub image_ids: HashMap<Rc<()>, ImageOrId>,
     count: u32,
@@ -44,6 +47,8 @@ impl ResourceManager {
             //image_ids: HashMap::new(),
             count: 0,
             fonts: HashMap::new(),
+            #[cfg(feature = "software")]
+            font_data: HashMap::new(),
         }
     }
 
@@ -0,0 +1,724 @@
+use std::time::{Duration, Instant};
+
+use crate::{
+    apply_clipping, apply_hover, apply_inline_inheritance, apply_layout, apply_portals,
+    apply_shared_inheritance, apply_styles, apply_text_constraints, apply_transform,
+    apply_visibility, apply_z_ordering, drag_cancel, drag_drop, drag_move, file_drop, file_hover,
+    file_hover_cancel, focus_nearest, focus_next, focus_prev, geometry_changed, menu_press,
+    scroll_to, tooltip_move, tooltip_press, tooltip_tick, BoundingBox, Canvas, Code, Color,
+    Context, Display, Entity, Event, EventManager, FontOrId, KeyChord, KeymapResult, Modifiers,
+    MouseButton, MouseButtonState, Propagation, PseudoClass, Units, Visibility, WindowEvent,
+};
+
+#[cfg(feature = "software")]
+use crate::SoftwareCanvas;
+
+/// Drives a [Context] through the frame pipeline.
+///
+/// The [Runtime] owns the per-frame sequence shared by all backends: rebuilding, font loading,
+/// event dispatch, observer updates, and the style, layout, transform, hover and clipping passes.
+/// A backend is only responsible for translating OS events into [WindowEvent]s, which are passed
+/// to [Runtime::handle_event], and for providing a canvas to [Runtime::render] into.
+pub struct Runtime {
+    event_manager: EventManager,
+
+    click_time: Instant,
+    double_click_interval: Duration,
+    double_click: bool,
+    click_pos: (f32, f32),
+}
+
+impl Runtime {
+    pub fn new() -> Self {
+        Self {
+            event_manager: EventManager::new(),
+
+            click_time: Instant::now(),
+            double_click_interval: Duration::from_millis(500),
+            double_click: false,
+            click_pos: (0.0, 0.0),
+        }
+    }
+
+    /// Sets up the root entity with the given window size and loads the default fonts.
+    pub fn init(&mut self, cx: &mut Context, width: u32, height: u32) {
+        let regular_font = include_bytes!("../../fonts/Roboto-Regular.ttf");
+        let bold_font = include_bytes!("../../fonts/Roboto-Bold.ttf");
+        let icon_font = include_bytes!("../../fonts/entypo.ttf");
+        let emoji_font = include_bytes!("../../fonts/OpenSansEmoji.ttf");
+        let arabic_font = include_bytes!("../../fonts/amiri-regular.ttf");
+        let material_font = include_bytes!("../../fonts/MaterialIcons-Regular.ttf");
+
+        cx.add_font_mem("roboto", regular_font);
+        cx.add_font_mem("roboto-bold", bold_font);
+        cx.add_font_mem("icons", icon_font);
+        cx.add_font_mem("emoji", emoji_font);
+        cx.add_font_mem("arabic", arabic_font);
+        cx.add_font_mem("material", material_font);
+
+        cx.style.default_font = "roboto".to_string();
+
+        cx.style.pseudo_classes.insert(Entity::root(), PseudoClass::default()).unwrap();
+        cx.style.disabled.insert(Entity::root(), false);
+
+        cx.cache.set_opacity(Entity::root(), 1.0);
+
+        self.set_window_size(cx, width as f32, height as f32);
+    }
+
+    /// Sets the size of the root entity and marks the application for restyle, relayout and redraw.
+    pub fn set_window_size(&mut self, cx: &mut Context, width: f32, height: f32) {
+        cx.style.width.insert(Entity::root(), Units::Pixels(width));
+        cx.style.height.insert(Entity::root(), Units::Pixels(height));
+
+        cx.cache.set_width(Entity::root(), width);
+        cx.cache.set_height(Entity::root(), height);
+
+        let mut bounding_box = BoundingBox::default();
+        bounding_box.w = width;
+        bounding_box.h = height;
+
+        cx.cache.set_clip_region(Entity::root(), bounding_box);
+
+        cx.style.needs_restyle = true;
+        cx.style.needs_relayout = true;
+        cx.style.needs_redraw = true;
+    }
+
+    /// Calls the builder if the application needs to be rebuilt.
+    pub fn rebuild<F>(&mut self, cx: &mut Context, builder: Option<&F>)
+    where
+        F: Fn(&mut Context) + ?Sized,
+    {
+        if cx.enviroment.needs_rebuild {
+            cx.current = Entity::root();
+            cx.count = 0;
+            if let Some(builder) = builder {
+                (builder)(cx);
+            }
+            cx.enviroment.needs_rebuild = false;
+        }
+    }
+
+    /// Loads any fonts which have been added to the resource manager since the last call.
+    ///
+    /// Fonts are always loaded into the text context of the [Context]. If a canvas is provided the
+    /// fonts are loaded into the canvas as well, in which case the font ids must match.
+    pub fn load_fonts(&mut self, cx: &mut Context, mut canvas: Option<&mut Canvas>) {
+        for (name, font) in cx.resource_manager.fonts.iter_mut() {
+            match font {
+                FontOrId::Font(data) => {
+                    let id = cx
+                        .text_context
+                        .add_font_mem(&data.clone())
+                        .expect(&format!("Failed to load font file for: {}", name));
+
+                    if let Some(canvas) = canvas.as_mut() {
+                        let canvas_id = canvas
+                            .add_font_mem(&data.clone())
+                            .expect(&format!("Failed to load font file for: {}", name));
+                        if canvas_id != id {
+                            panic!(
+                                "Fonts in canvas must have the same id as fonts in the text context"
+                            );
+                        }
+                    }
+
+                    *font = FontOrId::Id(id);
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Runs a single frame update.
+    ///
+    /// Flushes the event queue, updates the observers of any changed model data, and then runs the
+    /// style, layout, transform, hover and clipping passes. Returns true if the application needs
+    /// to be redrawn.
+    pub fn on_frame_update(&mut self, cx: &mut Context) -> bool {
+        // Events sent from other threads
+        cx.event_queue.extend(cx.proxy_receiver.try_iter());
+
+        // Timers
+        cx.timers.tick(Instant::now(), &mut cx.event_queue);
+
+        // Tooltips
+        tooltip_tick(cx, Instant::now());
+
+        // Events
+        while !cx.event_queue.is_empty() {
+            self.event_manager.flush_events(cx);
+        }
+
+        // Data Updates
+        let mut observers: Vec<Entity> = Vec::new();
+
+        for model_store in cx.data.dense.iter_mut().map(|entry| &mut entry.value) {
+            for (_, lens) in model_store.lenses.iter_mut() {
+                for (_, model) in model_store.data.iter() {
+                    if lens.update(model) {
+                        observers.extend(lens.observers().iter());
+                    }
+                }
+            }
+        }
+
+        for observer in observers.iter() {
+            if let Some(mut view) = cx.views.remove(observer) {
+                let prev = cx.current;
+                cx.current = *observer;
+                let prev_count = cx.count;
+                cx.count = 0;
+                view.body(cx);
+                cx.current = prev;
+                cx.count = prev_count;
+
+                cx.style.needs_redraw = true;
+
+                cx.views.insert(*observer, view);
+            }
+        }
+
+        // Not ideal
+        let tree = cx.tree.clone();
+
+        apply_inline_inheritance(cx, &tree);
+
+        if cx.style.needs_restyle {
+            apply_styles(cx, &tree);
+            cx.style.needs_restyle = false;
+        }
+
+        apply_shared_inheritance(cx, &tree);
+
+        apply_z_ordering(cx, &tree);
+
+        apply_visibility(cx, &tree);
+
+        apply_text_constraints(cx, &tree);
+
+        // Layout
+        if cx.style.needs_relayout {
+            apply_layout(&mut cx.cache, &cx.tree, &cx.style);
+            cx.style.needs_relayout = false;
+        }
+
+        // Emit any geometry changed events
+        geometry_changed(cx, &tree);
+
+        // Events which were deferred until after layout
+        self.event_manager.flush_deferred_events(cx);
+
+        apply_transform(cx, &tree);
+
+        // Portals are positioned from the transformed bounds of their anchors
+        if apply_portals(cx) {
+            apply_layout(&mut cx.cache, &cx.tree, &cx.style);
+            cx.style.needs_relayout = false;
+
+            geometry_changed(cx, &tree);
+
+            apply_transform(cx, &tree);
+        }
+
+        apply_hover(cx);
+
+        apply_clipping(cx, &tree);
+
+        let needs_redraw = cx.style.needs_redraw;
+        cx.style.needs_redraw = false;
+
+        needs_redraw
+    }
+
+    /// Calls the idle callback, if there is one.
+    pub fn on_idle<F>(&mut self, cx: &mut Context, on_idle: Option<&F>)
+    where
+        F: Fn(&mut Context) + ?Sized,
+    {
+        if let Some(idle_callback) = on_idle {
+            cx.current = Entity::root();
+            cx.count = 0;
+            (idle_callback)(cx);
+        }
+    }
+
+    /// Draws all of the visible views to the canvas.
+    pub fn render(&mut self, cx: &mut Context, canvas: &mut Canvas, dpi_factor: f32) {
+        let window_width = cx.cache.get_width(Entity::root());
+        let window_height = cx.cache.get_height(Entity::root());
+
+        canvas.set_size(window_width as u32, window_height as u32, dpi_factor);
+        let clear_color =
+            cx.style.background_color.get(Entity::root()).cloned().unwrap_or(Color::white());
+        canvas.clear_rect(0, 0, window_width as u32, window_height as u32, clear_color.into());
+
+        // Sort the tree by z order
+        let mut draw_tree: Vec<Entity> = cx.tree.into_iter().collect();
+        draw_tree.sort_by_cached_key(|entity| cx.cache.get_z_index(*entity));
+
+        for entity in draw_tree.into_iter() {
+            // Skip window
+            if entity == Entity::root() {
+                continue;
+            }
+
+            // Skip invisible widgets
+            if cx.cache.get_visibility(entity) == Visibility::Invisible {
+                continue;
+            }
+
+            // Skip non-displayed widgets
+            if cx.cache.get_display(entity) == Display::None {
+                continue;
+            }
+
+            // Skip widgets that have 0 opacity
+            if cx.cache.get_opacity(entity) == 0.0 {
+                continue;
+            }
+
+            let bounds = cx.cache.get_bounds(entity);
+
+            if bounds.x > window_width || bounds.y > window_height {
+                continue;
+            }
+
+            // Apply clipping
+            let clip_region = cx.cache.get_clip_region(entity);
+            canvas.scissor(clip_region.x, clip_region.y, clip_region.w, clip_region.h);
+
+            // Apply transform
+            let transform = cx.cache.get_transform(entity);
+            canvas.save();
+            canvas.set_transform(
+                transform[0],
+                transform[1],
+                transform[2],
+                transform[3],
+                transform[4],
+                transform[5],
+            );
+
+            if let Some(view) = cx.views.remove(&entity) {
+                cx.current = entity;
+                view.draw(cx, canvas);
+
+                cx.views.insert(entity, view);
+            }
+
+            canvas.restore();
+        }
+
+        canvas.flush();
+    }
+
+    /// Draws all of the visible views to a software canvas, resizing the canvas to the window size.
+    ///
+    /// Nothing is drawn while the window has no area, e.g. when it is minimized.
+    #[cfg(feature = "software")]
+    pub fn render_software(&mut self, cx: &mut Context, canvas: &mut SoftwareCanvas) {
+        let window_width = cx.cache.get_width(Entity::root());
+        let window_height = cx.cache.get_height(Entity::root());
+
+        if window_width < 1.0 || window_height < 1.0 {
+            return;
+        }
+
+        canvas.set_size(window_width as u32, window_height as u32);
+        canvas.reset_scissor();
+        canvas.set_transform(tiny_skia::Transform::identity());
+
+        let clear_color =
+            cx.style.background_color.get(Entity::root()).cloned().unwrap_or(Color::white());
+        canvas.clear(clear_color);
+
+        // Sort the tree by z order
+        let mut draw_tree: Vec<Entity> = cx.tree.into_iter().collect();
+        draw_tree.sort_by_cached_key(|entity| cx.cache.get_z_index(*entity));
+
+        for entity in draw_tree.into_iter() {
+            // Skip window
+            if entity == Entity::root() {
+                continue;
+            }
+
+            // Skip invisible widgets
+            if cx.cache.get_visibility(entity) == Visibility::Invisible {
+                continue;
+            }
+
+            // Skip non-displayed widgets
+            if cx.cache.get_display(entity) == Display::None {
+                continue;
+            }
+
+            // Skip widgets that have 0 opacity
+            if cx.cache.get_opacity(entity) == 0.0 {
+                continue;
+            }
+
+            let bounds = cx.cache.get_bounds(entity);
+
+            if bounds.x > window_width || bounds.y > window_height {
+                continue;
+            }
+
+            // Apply clipping
+            let clip_region = cx.cache.get_clip_region(entity);
+            canvas.scissor(clip_region.x, clip_region.y, clip_region.w, clip_region.h);
+
+            // Apply transform
+            let transform = cx.cache.get_transform(entity);
+            canvas.set_transform(tiny_skia::Transform::from_row(
+                transform[0],
+                transform[1],
+                transform[2],
+                transform[3],
+                transform[4],
+                transform[5],
+            ));
+
+            if let Some(view) = cx.views.remove(&entity) {
+                cx.current = entity;
+                view.draw_software(cx, canvas);
+
+                cx.views.insert(entity, view);
+            }
+        }
+
+        canvas.reset_scissor();
+        canvas.set_transform(tiny_skia::Transform::identity());
+    }
+
+    /// Routes a window event, translated from an OS event by the backend, to the relevant entity.
+    ///
+    /// Mouse events are sent to the captured entity, or the hovered entity if there is no captured
+    /// entity. Keyboard events are sent to the focused entity. Any other events are sent to the root.
+    pub fn handle_event(&mut self, cx: &mut Context, event: WindowEvent) {
+        match event {
+            WindowEvent::MouseMove(x, y) => {
+                cx.mouse.cursorx = x;
+                cx.mouse.cursory = y;
+
+                apply_hover(cx);
+                tooltip_move(cx);
+
+                if cx.captured != Entity::null() {
+                    cx.event_queue.push_back(
+                        Event::new(WindowEvent::MouseMove(x, y))
+                            .target(cx.captured)
+                            .propagate(Propagation::Direct),
+                    );
+                } else if cx.hovered != Entity::root() {
+                    cx.event_queue
+                        .push_back(Event::new(WindowEvent::MouseMove(x, y)).target(cx.hovered));
+                }
+
+                if cx.mouse.left.state == MouseButtonState::Pressed {
+                    drag_move(cx);
+                }
+            }
+
+            WindowEvent::MouseDown(button) => {
+                match button {
+                    MouseButton::Left => cx.mouse.left.state = MouseButtonState::Pressed,
+                    MouseButton::Right => cx.mouse.right.state = MouseButtonState::Pressed,
+                    MouseButton::Middle => cx.mouse.middle.state = MouseButtonState::Pressed,
+                    _ => {}
+                }
+
+                let new_click_time = Instant::now();
+                let click_duration = new_click_time - self.click_time;
+                let new_click_pos = (cx.mouse.cursorx, cx.mouse.cursory);
+
+                if click_duration <= self.double_click_interval && new_click_pos == self.click_pos {
+                    if !self.double_click {
+                        send_mouse_event(cx, WindowEvent::MouseDoubleClick(button));
+                        self.double_click = true;
+                    }
+                } else {
+                    self.double_click = false;
+                }
+
+                self.click_time = new_click_time;
+                self.click_pos = new_click_pos;
+
+                tooltip_press(cx);
+
+                send_mouse_event(cx, WindowEvent::MouseDown(button));
+
+                let pos_down = (cx.mouse.cursorx, cx.mouse.cursory);
+                let hovered = cx.hovered;
+
+                if button == MouseButton::Left {
+                    focus_nearest(cx, hovered);
+                    set_active(cx, hovered, true);
+                }
+
+                menu_press(cx, button);
+
+                match button {
+                    MouseButton::Left => {
+                        cx.mouse.left.pos_down = pos_down;
+                        cx.mouse.left.pressed = hovered;
+                    }
+
+                    MouseButton::Right => {
+                        cx.mouse.right.pos_down = pos_down;
+                        cx.mouse.right.pressed = hovered;
+                    }
+
+                    MouseButton::Middle => {
+                        cx.mouse.middle.pos_down = pos_down;
+                        cx.mouse.middle.pressed = hovered;
+                    }
+
+                    _ => {}
+                }
+            }
+
+            WindowEvent::MouseUp(button) => {
+                match button {
+                    MouseButton::Left => cx.mouse.left.state = MouseButtonState::Released,
+                    MouseButton::Right => cx.mouse.right.state = MouseButtonState::Released,
+                    MouseButton::Middle => cx.mouse.middle.state = MouseButtonState::Released,
+                    _ => {}
+                }
+
+                send_mouse_event(cx, WindowEvent::MouseUp(button));
+
+                if button == MouseButton::Left {
+                    set_active(cx, cx.mouse.left.pressed, false);
+                    drag_drop(cx);
+                }
+
+                let pos_up = (cx.mouse.cursorx, cx.mouse.cursory);
+                let hovered = cx.hovered;
+                match button {
+                    MouseButton::Left => {
+                        cx.mouse.left.pos_up = pos_up;
+                        cx.mouse.left.released = hovered;
+                    }
+
+                    MouseButton::Right => {
+                        cx.mouse.right.pos_up = pos_up;
+                        cx.mouse.right.released = hovered;
+                    }
+
+                    MouseButton::Middle => {
+                        cx.mouse.middle.pos_up = pos_up;
+                        cx.mouse.middle.released = hovered;
+                    }
+
+                    _ => {}
+                }
+            }
+
+            WindowEvent::MouseScroll(x, y) => {
+                send_mouse_event(cx, WindowEvent::MouseScroll(x, y));
+            }
+
+            WindowEvent::KeyDown(code, key) => {
+                if code == Code::F5 {
+                    cx.reload_styles().unwrap();
+                }
+
+                // Escape cancels a drag operation instead of being sent to views
+                if code == Code::Escape && cx.drag.is_dragging() {
+                    drag_cancel(cx);
+                    return;
+                }
+
+                // Keys which complete, or are the start of, a key binding are not sent to views
+                if !is_modifier_key(code) {
+                    let chord = KeyChord::new(cx.modifiers, code);
+                    match cx.keymap.resolve(&cx.tree, cx.focused, chord) {
+                        KeymapResult::Action(event) => {
+                            cx.event_queue.push_back(event);
+                            return;
+                        }
+
+                        KeymapResult::Callback(callback) => {
+                            cx.with_current(cx.focused, |cx| (callback)(cx));
+                            return;
+                        }
+
+                        KeymapResult::Pending => return,
+
+                        KeymapResult::None => {}
+                    }
+                }
+
+                send_key_event(cx, WindowEvent::KeyDown(code, key));
+
+                // Move focus with Tab and Shift+Tab
+                if code == Code::Tab {
+                    if cx.modifiers.is_empty() {
+                        focus_next(cx);
+                    } else if cx.modifiers == Modifiers::SHIFT {
+                        focus_prev(cx);
+                    }
+
+                    let focused = cx.focused;
+                    scroll_to(cx, focused);
+                }
+            }
+
+            WindowEvent::KeyUp(code, key) => {
+                send_key_event(cx, WindowEvent::KeyUp(code, key));
+            }
+
+            WindowEvent::CharInput(character) => {
+                cx.event_queue.push_back(
+                    Event::new(WindowEvent::CharInput(character))
+                        .target(cx.focused)
+                        .propagate(Propagation::DownUp),
+                );
+            }
+
+            WindowEvent::FileHovered(path) => {
+                file_hover(cx);
+
+                cx.event_queue.push_back(
+                    Event::new(WindowEvent::FileHovered(path))
+                        .target(cx.hovered)
+                        .propagate(Propagation::Up),
+                );
+            }
+
+            WindowEvent::FileDropped(path) => {
+                file_drop(cx, path.clone());
+
+                cx.event_queue.push_back(
+                    Event::new(WindowEvent::FileDropped(path))
+                        .target(cx.hovered)
+                        .propagate(Propagation::Up),
+                );
+            }
+
+            WindowEvent::FileHoverCancelled => {
+                file_hover_cancel(cx);
+
+                cx.event_queue.push_back(
+                    Event::new(WindowEvent::FileHoverCancelled)
+                        .target(cx.hovered)
+                        .propagate(Propagation::Up),
+                );
+            }
+
+            WindowEvent::WindowResize(width, height) => {
+                self.set_window_size(cx, width, height);
+
+                cx.event_queue.push_back(
+                    Event::new(WindowEvent::WindowResize(width, height)).target(Entity::root()),
+                );
+            }
+
+            event => {
+                cx.event_queue.push_back(Event::new(event).target(Entity::root()));
+            }
+        }
+    }
+}
+
+// Sends a mouse event to the captured entity, or to the hovered entity if no entity has captured the mouse
+fn send_mouse_event(cx: &mut Context, event: WindowEvent) {
+    if cx.captured != Entity::null() {
+        cx.event_queue
+            .push_back(Event::new(event).target(cx.captured).propagate(Propagation::Direct));
+    } else {
+        cx.event_queue
+            .push_back(Event::new(event).target(cx.hovered).propagate(Propagation::DownUp));
+    }
+}
+
+// Returns true if the key is a modifier key, which does not form a key chord by itself
+fn is_modifier_key(code: Code) -> bool {
+    matches!(
+        code,
+        Code::ShiftLeft
+            | Code::ShiftRight
+            | Code::ControlLeft
+            | Code::ControlRight
+            | Code::AltLeft
+            | Code::AltRight
+            | Code::MetaLeft
+            | Code::MetaRight
+    )
+}
+
+// Sends a keyboard event to the focused entity, or to the hovered entity if no entity is focused
+fn send_key_event(cx: &mut Context, event: WindowEvent) {
+    let target = if cx.focused != Entity::null() { cx.focused } else { cx.hovered };
+
+    cx.event_queue.push_back(Event::new(event).target(target).propagate(Propagation::DownUp));
+}
+
+// Sets the active state of an entity and its ancestors, which are active while the mouse is pressed on the entity
+fn set_active(cx: &mut Context, entity: Entity, active: bool) {
+    let ancestors = entity.parent_iter(&cx.tree).collect::<Vec<_>>();
+
+    for ancestor in ancestors {
+        if let Some(pseudo_classes) = cx.style.pseudo_classes.get_mut(ancestor) {
+            pseudo_classes.set(PseudoClass::ACTIVE, active);
+        }
+    }
+
+    cx.style.needs_restyle = true;
+    cx.style.needs_redraw = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Element, Units::*};
+
+    // Sets up a context and runtime for a 200x100 window, as a backend would
+    fn setup<F: Fn(&mut Context)>(builder: F) -> (Context, Runtime) {
+        let mut cx = Context::new();
+        cx.entity_manager.create();
+
+        let mut runtime = Runtime::new();
+        runtime.init(&mut cx, 200, 100);
+        runtime.rebuild(&mut cx, Some(&builder));
+        runtime.load_fonts(&mut cx, None);
+
+        (cx, runtime)
+    }
+
+    #[test]
+    fn frame_update_lays_out_views() {
+        let (mut cx, mut runtime) = setup(|cx| {
+            Element::new(cx).width(Pixels(50.0)).height(Stretch(1.0));
+        });
+
+        assert!(runtime.on_frame_update(&mut cx));
+
+        let element = cx.tree.get_first_child(Entity::root()).expect("Failed to get element");
+        assert_eq!((cx.cache.get_width(element), cx.cache.get_height(element)), (50.0, 100.0));
+
+        runtime.handle_event(&mut cx, WindowEvent::WindowResize(200.0, 300.0));
+        runtime.on_frame_update(&mut cx);
+
+        assert_eq!(cx.cache.get_height(element), 300.0);
+    }
+
+    #[test]
+    fn mouse_move_updates_hovered_view() {
+        let (mut cx, mut runtime) = setup(|cx| {
+            Element::new(cx).width(Pixels(50.0)).height(Pixels(50.0));
+        });
+
+        runtime.on_frame_update(&mut cx);
+        let element = cx.tree.get_first_child(Entity::root()).expect("Failed to get element");
+
+        runtime.handle_event(&mut cx, WindowEvent::MouseMove(10.0, 10.0));
+        assert_eq!(cx.hovered, element);
+
+        runtime.handle_event(&mut cx, WindowEvent::MouseMove(100.0, 10.0));
+        assert_eq!(cx.hovered, Entity::root());
+    }
+}
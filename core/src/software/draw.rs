@@ -0,0 +1,462 @@
+use ab_glyph::{point, Font, FontArc, Glyph, PxScale, ScaleFont};
+use morphorm::Units;
+use tiny_skia::{
+    GradientStop, LinearGradient, Paint, Path, PathBuilder, Pixmap, Point, PremultipliedColorU8,
+    SpreadMode, Stroke, Transform,
+};
+
+use super::{blur, to_sk_color, SoftwareCanvas};
+use crate::{
+    style::{BorderCornerShape, GradientDirection},
+    Context,
+};
+
+// Length proportional to radius of a cubic bezier handle for 90deg arcs.
+const KAPPA90: f32 = 0.5522847493;
+
+/// Draws the background, border, outer shadow and text of the current view to a software canvas.
+///
+/// This is the default implementation of [View::draw_software](crate::View::draw_software) and
+/// matches the output of the default [View::draw](crate::View::draw).
+pub fn draw_view(cx: &mut Context, canvas: &mut SoftwareCanvas) {
+    let entity = cx.current;
+
+    let bounds = cx.cache.get_bounds(entity);
+
+    //Skip widgets with no width or no height
+    if bounds.w == 0.0 || bounds.h == 0.0 {
+        return;
+    }
+
+    let background_color = cx.style.background_color.get(entity).cloned().unwrap_or_default();
+
+    let font_color = cx.style.font_color.get(entity).cloned().unwrap_or(crate::Color::rgb(0, 0, 0));
+
+    let border_color = cx.style.border_color.get(entity).cloned().unwrap_or_default();
+
+    let border_shape_top_left =
+        cx.style.border_shape_top_left.get(entity).cloned().unwrap_or_default();
+
+    let border_shape_top_right =
+        cx.style.border_shape_top_right.get(entity).cloned().unwrap_or_default();
+
+    let border_shape_bottom_left =
+        cx.style.border_shape_bottom_left.get(entity).cloned().unwrap_or_default();
+
+    let border_shape_bottom_right =
+        cx.style.border_shape_bottom_right.get(entity).cloned().unwrap_or_default();
+
+    let border_radius = |radius: Option<&Units>| match radius.cloned().unwrap_or_default() {
+        Units::Pixels(val) => val,
+        Units::Percentage(val) => bounds.w.min(bounds.h) * (val / 100.0),
+        _ => 0.0,
+    };
+
+    let border_radius_top_left = border_radius(cx.style.border_radius_top_left.get(entity));
+    let border_radius_top_right = border_radius(cx.style.border_radius_top_right.get(entity));
+    let border_radius_bottom_left = border_radius(cx.style.border_radius_bottom_left.get(entity));
+    let border_radius_bottom_right = border_radius(cx.style.border_radius_bottom_right.get(entity));
+
+    let opacity = cx.cache.get_opacity(entity);
+
+    let border_width = match cx.style.border_width.get(entity).cloned().unwrap_or_default() {
+        Units::Pixels(val) => val,
+        Units::Percentage(val) => bounds.w.min(bounds.h) * (val / 100.0),
+        _ => 0.0,
+    };
+
+    let shadow_length = |length: Option<&Units>| match length.cloned().unwrap_or_default() {
+        Units::Pixels(val) => val,
+        Units::Percentage(val) => bounds.w * (val / 100.0),
+        _ => 0.0,
+    };
+
+    let outer_shadow_h_offset = shadow_length(cx.style.outer_shadow_h_offset.get(entity));
+    let outer_shadow_v_offset = shadow_length(cx.style.outer_shadow_v_offset.get(entity));
+    let outer_shadow_blur = shadow_length(cx.style.outer_shadow_blur.get(entity));
+
+    let x = bounds.x + border_width / 2.0;
+    let y = bounds.y + border_width / 2.0;
+    let w = bounds.w - border_width;
+    let h = bounds.h - border_width;
+
+    let path = if border_radius_bottom_left == (bounds.w - 2.0 * border_width) / 2.0
+        && border_radius_bottom_right == (bounds.w - 2.0 * border_width) / 2.0
+        && border_radius_top_left == (bounds.w - 2.0 * border_width) / 2.0
+        && border_radius_top_right == (bounds.w - 2.0 * border_width) / 2.0
+    {
+        PathBuilder::from_circle(x + w / 2.0, y + h / 2.0, bounds.w / 2.0)
+    } else {
+        let halfw = w.abs() * 0.5;
+        let halfh = h.abs() * 0.5;
+
+        let rx_bl = border_radius_bottom_left.min(halfw) * w.signum();
+        let ry_bl = border_radius_bottom_left.min(halfh) * h.signum();
+
+        let rx_br = border_radius_bottom_right.min(halfw) * w.signum();
+        let ry_br = border_radius_bottom_right.min(halfh) * h.signum();
+
+        let rx_tr = border_radius_top_right.min(halfw) * w.signum();
+        let ry_tr = border_radius_top_right.min(halfh) * h.signum();
+
+        let rx_tl = border_radius_top_left.min(halfw) * w.signum();
+        let ry_tl = border_radius_top_left.min(halfh) * h.signum();
+
+        let mut path = PathBuilder::new();
+
+        path.move_to(x, y + ry_tl);
+        path.line_to(x, y + h - ry_bl);
+        if border_radius_bottom_left != 0.0 {
+            if border_shape_bottom_left == BorderCornerShape::Round {
+                path.cubic_to(
+                    x,
+                    y + h - ry_bl * (1.0 - KAPPA90),
+                    x + rx_bl * (1.0 - KAPPA90),
+                    y + h,
+                    x + rx_bl,
+                    y + h,
+                );
+            } else {
+                path.line_to(x + rx_bl, y + h);
+            }
+        }
+
+        path.line_to(x + w - rx_br, y + h);
+
+        if border_radius_bottom_right != 0.0 {
+            if border_shape_bottom_right == BorderCornerShape::Round {
+                path.cubic_to(
+                    x + w - rx_br * (1.0 - KAPPA90),
+                    y + h,
+                    x + w,
+                    y + h - ry_br * (1.0 - KAPPA90),
+                    x + w,
+                    y + h - ry_br,
+                );
+            } else {
+                path.line_to(x + w, y + h - ry_br);
+            }
+        }
+
+        path.line_to(x + w, y + ry_tr);
+
+        if border_radius_top_right != 0.0 {
+            if border_shape_top_right == BorderCornerShape::Round {
+                path.cubic_to(
+                    x + w,
+                    y + ry_tr * (1.0 - KAPPA90),
+                    x + w - rx_tr * (1.0 - KAPPA90),
+                    y,
+                    x + w - rx_tr,
+                    y,
+                );
+            } else {
+                path.line_to(x + w - rx_tr, y);
+            }
+        }
+
+        path.line_to(x + rx_tl, y);
+
+        if border_radius_top_left != 0.0 {
+            if border_shape_top_left == BorderCornerShape::Round {
+                path.cubic_to(
+                    x + rx_tl * (1.0 - KAPPA90),
+                    y,
+                    x,
+                    y + ry_tl * (1.0 - KAPPA90),
+                    x,
+                    y + ry_tl,
+                );
+            } else {
+                path.line_to(x, y + ry_tl);
+            }
+        }
+
+        path.close();
+
+        path.finish()
+    };
+
+    let path = match path {
+        Some(path) => path,
+        None => return,
+    };
+
+    // Draw outer shadow
+    if let Some(outer_shadow_color) = cx.style.outer_shadow_color.get(entity).cloned() {
+        draw_shadow(
+            canvas,
+            &path,
+            to_sk_color(outer_shadow_color, opacity),
+            (bounds.x, bounds.y, bounds.w, bounds.h),
+            (outer_shadow_h_offset, outer_shadow_v_offset),
+            outer_shadow_blur / 2.0,
+        );
+    }
+
+    // Fill with background color
+    let mut paint = Paint::default();
+    paint.anti_alias = true;
+    paint.set_color(to_sk_color(background_color, opacity));
+
+    // Gradient overrides background color
+    if let Some(background_gradient) = cx.style.background_gradient.get(entity) {
+        let parent = cx.tree.get_parent(entity).unwrap_or(entity);
+
+        let (end_x, end_y, parent_length) = match background_gradient.direction {
+            GradientDirection::LeftToRight => (bounds.w, 0.0, cx.cache.get_width(parent)),
+            GradientDirection::TopToBottom => (0.0, bounds.h, cx.cache.get_height(parent)),
+            _ => (bounds.w, 0.0, cx.cache.get_width(parent)),
+        };
+
+        let stops = background_gradient
+            .get_stops(parent_length)
+            .iter()
+            .map(|stop| GradientStop::new(stop.0, to_sk_color(stop.1, opacity)))
+            .collect::<Vec<_>>();
+
+        if let Some(shader) = LinearGradient::new(
+            Point::from_xy(bounds.x, bounds.y),
+            Point::from_xy(bounds.x + end_x, bounds.y + end_y),
+            stops,
+            SpreadMode::Pad,
+            Transform::identity(),
+        ) {
+            paint.shader = shader;
+        }
+    }
+
+    // Fill the quad
+    canvas.fill_path(&path, &paint);
+
+    // Draw border
+    if border_width > 0.0 {
+        let mut paint = Paint::default();
+        paint.anti_alias = true;
+        paint.set_color(to_sk_color(border_color, opacity));
+        let stroke = Stroke { width: border_width, ..Stroke::default() };
+        canvas.stroke_path(&path, &paint, &stroke);
+    }
+
+    // Draw text
+    if let Some(text) = cx.style.text.get(entity).cloned() {
+        let font_name = cx.style.font.get(entity).cloned().unwrap_or_default();
+        let default_font = cx.style.default_font.clone();
+
+        let font = match canvas.font(cx, &font_name).or_else(|| canvas.font(cx, &default_font)) {
+            Some(font) => font,
+            None => return,
+        };
+
+        let mut x = bounds.x;
+        let mut y = bounds.y;
+
+        // TODO - Move this to a text layout system and include constraints
+        let child_left = cx.style.child_left.get(entity).cloned().unwrap_or_default();
+        let child_right = cx.style.child_right.get(entity).cloned().unwrap_or_default();
+        let child_top = cx.style.child_top.get(entity).cloned().unwrap_or_default();
+        let child_bottom = cx.style.child_bottom.get(entity).cloned().unwrap_or_default();
+
+        // Horizontal alignment, as a fraction of the text width to shift the text left by
+        let align = match child_left {
+            Units::Pixels(val) => match child_right {
+                Units::Stretch(_) | Units::Auto => {
+                    x += val + border_width;
+                    0.0
+                }
+
+                _ => 0.0,
+            },
+
+            Units::Stretch(_) => match child_right {
+                Units::Pixels(val) => {
+                    x += bounds.w - val - border_width;
+                    1.0
+                }
+
+                Units::Stretch(_) => {
+                    x += 0.5 * bounds.w;
+                    0.5
+                }
+
+                _ => 1.0,
+            },
+
+            _ => 0.0,
+        };
+
+        let font_size = cx.style.font_size.get(entity).cloned().unwrap_or(16.0);
+        let scaled_font = font.as_scaled(PxScale::from(font_size));
+
+        let baseline = match child_top {
+            Units::Pixels(val) => match child_bottom {
+                Units::Stretch(_) | Units::Auto => {
+                    y += val + border_width;
+                    scaled_font.ascent()
+                }
+
+                _ => scaled_font.ascent(),
+            },
+
+            Units::Stretch(_) => match child_bottom {
+                Units::Pixels(val) => {
+                    y += bounds.h - val - border_width;
+                    scaled_font.descent()
+                }
+
+                Units::Stretch(_) => {
+                    y += 0.5 * bounds.h;
+                    (scaled_font.ascent() + scaled_font.descent()) / 2.0
+                }
+
+                _ => scaled_font.descent(),
+            },
+
+            _ => scaled_font.ascent(),
+        };
+
+        let glyphs = layout_text(&font, font_size, &text);
+        let text_width = glyphs
+            .last()
+            .map(|glyph| glyph.position.x + scaled_font.h_advance(glyph.id))
+            .unwrap_or_default();
+
+        draw_glyphs(
+            canvas,
+            &font,
+            glyphs,
+            (x - text_width * align).round(),
+            (y + baseline).round(),
+            to_sk_color(font_color, opacity),
+        );
+    }
+}
+
+// Draws a blurred copy of a path, offset from the bounds of the view
+fn draw_shadow(
+    canvas: &mut SoftwareCanvas,
+    path: &Path,
+    color: tiny_skia::Color,
+    bounds: (f32, f32, f32, f32),
+    offset: (f32, f32),
+    sigma: f32,
+) {
+    let (x, y, w, h) = bounds;
+    let d = (sigma * 5.0).ceil();
+
+    let mut shadow = match Pixmap::new((w + d).ceil() as u32, (h + d).ceil() as u32) {
+        Some(shadow) => shadow,
+        None => return,
+    };
+
+    let mut paint = Paint::default();
+    paint.anti_alias = true;
+    paint.set_color(color);
+
+    shadow.fill_path(
+        path,
+        &paint,
+        tiny_skia::FillRule::Winding,
+        Transform::from_translate(-x + d / 2.0, -y + d / 2.0),
+        None,
+    );
+
+    blur(&mut shadow, sigma);
+
+    canvas.draw_pixmap(
+        (x - d / 2.0 + offset.0).round() as i32,
+        (y - d / 2.0 + offset.1).round() as i32,
+        shadow.as_ref(),
+    );
+}
+
+// Positions the glyphs of a single line of text, starting at the origin
+fn layout_text(font: &FontArc, font_size: f32, text: &str) -> Vec<Glyph> {
+    let scaled_font = font.as_scaled(PxScale::from(font_size));
+
+    let mut glyphs = Vec::new();
+    let mut caret = 0.0;
+    let mut previous: Option<Glyph> = None;
+
+    for character in text.chars() {
+        let mut glyph = scaled_font.scaled_glyph(character);
+
+        if let Some(previous) = previous.take() {
+            caret += scaled_font.kern(previous.id, glyph.id);
+        }
+
+        glyph.position = point(caret, 0.0);
+        caret += scaled_font.h_advance(glyph.id);
+
+        previous = Some(glyph.clone());
+        glyphs.push(glyph);
+    }
+
+    glyphs
+}
+
+// Rasterizes glyphs into a coverage image and draws it to the canvas with the given color
+fn draw_glyphs(
+    canvas: &mut SoftwareCanvas,
+    font: &FontArc,
+    glyphs: Vec<Glyph>,
+    x: f32,
+    y: f32,
+    color: tiny_skia::Color,
+) {
+    let outlines =
+        glyphs.into_iter().filter_map(|glyph| font.outline_glyph(glyph)).collect::<Vec<_>>();
+
+    if outlines.is_empty() {
+        return;
+    }
+
+    // Bounds of all of the glyphs relative to the text origin
+    let min_x = outlines.iter().map(|outline| outline.px_bounds().min.x).fold(f32::MAX, f32::min);
+    let min_y = outlines.iter().map(|outline| outline.px_bounds().min.y).fold(f32::MAX, f32::min);
+    let max_x = outlines.iter().map(|outline| outline.px_bounds().max.x).fold(f32::MIN, f32::max);
+    let max_y = outlines.iter().map(|outline| outline.px_bounds().max.y).fold(f32::MIN, f32::max);
+
+    let width = (max_x - min_x).ceil() as u32;
+    let height = (max_y - min_y).ceil() as u32;
+
+    let mut image = match Pixmap::new(width, height) {
+        Some(image) => image,
+        None => return,
+    };
+
+    let color = color.premultiply();
+    let pixels = image.pixels_mut();
+
+    for outline in outlines.iter() {
+        let glyph_bounds = outline.px_bounds();
+        let offset_x = (glyph_bounds.min.x - min_x) as u32;
+        let offset_y = (glyph_bounds.min.y - min_y) as u32;
+
+        outline.draw(|gx, gy, coverage| {
+            let px = gx + offset_x;
+            let py = gy + offset_y;
+
+            if px >= width || py >= height {
+                return;
+            }
+
+            let pixel = &mut pixels[(py * width + px) as usize];
+            let alpha = (coverage.min(1.0) * color.alpha() * 255.0).round() as u8;
+
+            // Glyphs may overlap, so keep the highest coverage
+            if alpha > pixel.alpha() {
+                let scale = coverage.min(1.0);
+                *pixel = PremultipliedColorU8::from_rgba(
+                    (color.red() * scale * 255.0).round() as u8,
+                    (color.green() * scale * 255.0).round() as u8,
+                    (color.blue() * scale * 255.0).round() as u8,
+                    alpha,
+                )
+                .unwrap_or(*pixel);
+            }
+        });
+    }
+
+    canvas.draw_pixmap((x + min_x) as i32, (y + min_y) as i32, image.as_ref());
+}
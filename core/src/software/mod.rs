@@ -0,0 +1,349 @@
+//! CPU rendering of views into an in-memory RGBA image.
+//!
+//! The software renderer is enabled with the `software` cargo feature and is intended for
+//! rendering on machines without a GPU, e.g. for screenshot tests. Views are drawn with
+//! [View::draw_software](crate::View::draw_software), which by default renders the background,
+//! border, border radii, gradient, outer shadow and text of the view, as the OpenGL renderer does.
+
+use std::collections::HashMap;
+
+use ab_glyph::FontArc;
+use tiny_skia::{
+    ClipMask, FillRule, Paint, Path, PathBuilder, Pixmap, PixmapPaint, PixmapRef, Stroke, Transform,
+};
+
+use crate::{Color, Context};
+
+mod draw;
+pub use draw::draw_view;
+
+/// A canvas which renders into an RGBA image in memory.
+///
+/// The transform and clip region of the canvas are set by the renderer before a view is drawn,
+/// and are applied by all of the drawing methods of the canvas.
+pub struct SoftwareCanvas {
+    pixmap: Pixmap,
+    transform: Transform,
+    clip_mask: Option<ClipMask>,
+    fonts: HashMap<String, FontArc>,
+}
+
+impl SoftwareCanvas {
+    /// Creates a new transparent canvas with the given size in pixels.
+    ///
+    /// # Panics
+    /// Panics if either the width or the height is zero.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            pixmap: Pixmap::new(width, height).expect("Failed to create canvas"),
+            transform: Transform::identity(),
+            clip_mask: None,
+            fonts: HashMap::new(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.pixmap.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.pixmap.height()
+    }
+
+    /// Returns the underlying pixmap, which can be used for drawing directly with tiny-skia.
+    pub fn pixmap(&self) -> &Pixmap {
+        &self.pixmap
+    }
+
+    pub fn pixmap_mut(&mut self) -> &mut Pixmap {
+        &mut self.pixmap
+    }
+
+    /// Returns the current transform of the canvas.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Returns the current clip mask of the canvas, if there is one.
+    pub fn clip_mask(&self) -> Option<&ClipMask> {
+        self.clip_mask.as_ref()
+    }
+
+    /// Returns the pixels of the canvas as non-premultiplied RGBA bytes, row by row.
+    pub fn to_rgba(&self) -> Vec<u8> {
+        self.pixmap
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let color = pixel.demultiply();
+                [color.red(), color.green(), color.blue(), color.alpha()]
+            })
+            .collect()
+    }
+
+    /// Resizes the canvas, clearing its contents.
+    pub fn set_size(&mut self, width: u32, height: u32) {
+        if width != self.pixmap.width() || height != self.pixmap.height() {
+            self.pixmap = Pixmap::new(width, height).expect("Failed to create canvas");
+        }
+    }
+
+    /// Fills the whole canvas with a color, ignoring the transform and clip region.
+    pub fn clear(&mut self, color: Color) {
+        self.pixmap.fill(to_sk_color(color, 1.0));
+    }
+
+    /// Sets the transform which is applied to everything drawn to the canvas.
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.transform = transform;
+    }
+
+    /// Restricts drawing to a rectangle, in canvas coordinates.
+    pub fn scissor(&mut self, x: f32, y: f32, w: f32, h: f32) {
+        self.clip_mask =
+            tiny_skia::Rect::from_xywh(x, y, w.max(0.0), h.max(0.0)).and_then(|rect| {
+                let mut clip_mask = ClipMask::new();
+                clip_mask.set_path(
+                    self.pixmap.width(),
+                    self.pixmap.height(),
+                    &PathBuilder::from_rect(rect),
+                    FillRule::Winding,
+                    false,
+                )?;
+                Some(clip_mask)
+            });
+    }
+
+    /// Removes the clip region.
+    pub fn reset_scissor(&mut self) {
+        self.clip_mask = None;
+    }
+
+    /// Fills a path, applying the transform and clip region of the canvas.
+    pub fn fill_path(&mut self, path: &Path, paint: &Paint) {
+        self.pixmap.fill_path(
+            path,
+            paint,
+            FillRule::Winding,
+            self.transform,
+            self.clip_mask.as_ref(),
+        );
+    }
+
+    /// Strokes a path, applying the transform and clip region of the canvas.
+    pub fn stroke_path(&mut self, path: &Path, paint: &Paint, stroke: &Stroke) {
+        self.pixmap.stroke_path(path, paint, stroke, self.transform, self.clip_mask.as_ref());
+    }
+
+    /// Draws a pixmap at a position, applying the transform and clip region of the canvas.
+    pub fn draw_pixmap(&mut self, x: i32, y: i32, pixmap: PixmapRef) {
+        self.pixmap.draw_pixmap(
+            x,
+            y,
+            pixmap,
+            &PixmapPaint::default(),
+            self.transform,
+            self.clip_mask.as_ref(),
+        );
+    }
+
+    // Returns the font with the given name, loading it from the resource manager if required
+    pub(crate) fn font(&mut self, cx: &Context, name: &str) -> Option<FontArc> {
+        if let Some(font) = self.fonts.get(name) {
+            return Some(font.clone());
+        }
+
+        let data = cx.resource_manager.font_data.get(name)?;
+        let font = FontArc::try_from_vec(data.clone()).ok()?;
+        self.fonts.insert(name.to_owned(), font.clone());
+
+        Some(font)
+    }
+}
+
+// Converts a vizia color to a tiny-skia color, multiplying the alpha by an opacity
+pub(crate) fn to_sk_color(color: Color, opacity: f32) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba8(
+        color.r(),
+        color.g(),
+        color.b(),
+        (color.a() as f32 * opacity).round() as u8,
+    )
+}
+
+// Approximates a gaussian blur of a pixmap with three passes of a box blur in each direction
+pub(crate) fn blur(pixmap: &mut Pixmap, sigma: f32) {
+    if sigma <= 0.0 {
+        return;
+    }
+
+    let width = pixmap.width() as usize;
+    let height = pixmap.height() as usize;
+
+    // Box size which approximates a gaussian with the given sigma over three passes
+    let radius = (((12.0 * sigma * sigma / 3.0) + 1.0).sqrt() / 2.0).floor() as usize;
+
+    if radius == 0 {
+        return;
+    }
+
+    let data = pixmap.data_mut();
+    let mut buffer = vec![0u8; data.len()];
+
+    for _ in 0..3 {
+        box_blur(data, &mut buffer, width, height, radius, 4, width * 4);
+        box_blur(&buffer, data, height, width, radius, width * 4, 4);
+    }
+}
+
+// Blurs `lines` lines of `len` pixels, where `step` is the byte offset between pixels in a line
+// and `stride` is the byte offset between lines
+fn box_blur(
+    src: &[u8],
+    dst: &mut [u8],
+    len: usize,
+    lines: usize,
+    radius: usize,
+    step: usize,
+    stride: usize,
+) {
+    let window = (2 * radius + 1) as u32;
+
+    for line in 0..lines {
+        let start = line * stride;
+
+        for channel in 0..4 {
+            let pixel = |i: isize| -> u32 {
+                let i = i.clamp(0, len as isize - 1) as usize;
+                src[start + i * step + channel] as u32
+            };
+
+            let mut sum: u32 = (-(radius as isize)..=radius as isize).map(pixel).sum();
+
+            for i in 0..len {
+                dst[start + i * step + channel] = (sum / window) as u8;
+                sum += pixel(i as isize + radius as isize + 1);
+                sum -= pixel(i as isize - radius as isize);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::{GradientDirection, GradientStop, LinearGradient};
+    use crate::{Element, Label, PropSet, Runtime, Units::*};
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const RED: [u8; 4] = [255, 0, 0, 255];
+    const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+    // Builds the views in a 100x100 window and renders them, returning the RGBA image
+    fn render<F: Fn(&mut Context)>(builder: F) -> Vec<u8> {
+        let mut cx = Context::new();
+        cx.entity_manager.create();
+
+        let mut runtime = Runtime::new();
+        runtime.init(&mut cx, 100, 100);
+        runtime.rebuild(&mut cx, Some(&builder));
+        runtime.load_fonts(&mut cx, None);
+        runtime.on_frame_update(&mut cx);
+
+        let mut canvas = SoftwareCanvas::new(100, 100);
+        runtime.render_software(&mut cx, &mut canvas);
+
+        canvas.to_rgba()
+    }
+
+    fn pixel(image: &[u8], x: usize, y: usize) -> [u8; 4] {
+        let index = (y * 100 + x) * 4;
+        [image[index], image[index + 1], image[index + 2], image[index + 3]]
+    }
+
+    #[test]
+    fn background_fills_bounds() {
+        let image = render(|cx| {
+            Element::new(cx).size(Pixels(50.0)).background_color(Color::rgb(255, 0, 0));
+        });
+
+        assert_eq!(pixel(&image, 0, 0), RED);
+        assert_eq!(pixel(&image, 49, 49), RED);
+        assert_eq!(pixel(&image, 50, 50), WHITE);
+    }
+
+    #[test]
+    fn border_radius_rounds_corners() {
+        let image = render(|cx| {
+            Element::new(cx)
+                .size(Pixels(100.0))
+                .background_color(Color::rgb(0, 0, 255))
+                .border_radius(Pixels(20.0));
+        });
+
+        assert_eq!(pixel(&image, 1, 1), WHITE);
+        assert_eq!(pixel(&image, 98, 98), WHITE);
+        assert_eq!(pixel(&image, 50, 1), BLUE);
+        assert_eq!(pixel(&image, 1, 50), BLUE);
+    }
+
+    #[test]
+    fn gradient_blends_between_stops() {
+        let image = render(|cx| {
+            let entity = Element::new(cx).size(Pixels(100.0)).entity;
+
+            entity.set_background_gradient(
+                cx,
+                LinearGradient::new(GradientDirection::LeftToRight)
+                    .add_stop(GradientStop::new(Percentage(0.0), Color::rgb(255, 0, 0)))
+                    .add_stop(GradientStop::new(Percentage(100.0), Color::rgb(0, 0, 255))),
+            );
+        });
+
+        let left = pixel(&image, 0, 50);
+        let middle = pixel(&image, 50, 50);
+        let right = pixel(&image, 99, 50);
+
+        assert!(left[0] > 240 && left[2] < 15);
+        assert!(right[0] < 15 && right[2] > 240);
+        assert!((middle[0] as i32 - middle[2] as i32).abs() < 10);
+    }
+
+    #[test]
+    fn text_is_drawn_centered() {
+        let image = render(|cx| {
+            Label::new(cx, "Hello").size(Pixels(100.0)).child_space(Stretch(1.0));
+        });
+
+        let is_ink = |x: usize, y: usize| pixel(&image, x, y)[0] < 128;
+
+        let ink =
+            (0..100).flat_map(|y| (0..100).map(move |x| (x, y))).filter(|(x, y)| is_ink(*x, *y));
+        assert!(ink.count() > 20);
+
+        // The text is centered, so the edges of the label are empty
+        assert!(
+            (0..100).all(|i| !is_ink(i, 0) && !is_ink(i, 99) && !is_ink(0, i) && !is_ink(99, i))
+        );
+    }
+
+    #[test]
+    fn zero_sized_window_is_not_drawn() {
+        let mut cx = Context::new();
+        cx.entity_manager.create();
+
+        let builder = |cx: &mut Context| {
+            Element::new(cx);
+        };
+
+        let mut runtime = Runtime::new();
+        runtime.init(&mut cx, 0, 0);
+        runtime.rebuild(&mut cx, Some(&builder));
+        runtime.on_frame_update(&mut cx);
+
+        let mut canvas = SoftwareCanvas::new(10, 10);
+        runtime.render_software(&mut cx, &mut canvas);
+
+        assert_eq!((canvas.width(), canvas.height()), (10, 10));
+    }
+}
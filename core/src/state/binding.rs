@@ -154,6 +154,13 @@ impl_res_simple!(char);
 impl_res_simple!(bool);
 impl_res_simple!(f32);
 impl_res_simple!(f64);
+impl_res_simple!(String);
+
+impl<'s> Res<&'s str> for &'s str {
+    fn get<'a>(&'a self, _: &'a Context) -> &'a &'s str {
+        self
+    }
+}
 
 impl<T, L> Res<T> for Field<L>
 where
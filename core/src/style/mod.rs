@@ -271,6 +271,7 @@ pub struct Style {
     pub default_font: String,
 
     pub elements: SparseSet<String>,
+    pub ids: SparseSet<String>,
     pub classes: SparseSet<HashSet<String>>,
     pub pseudo_classes: SparseSet<PseudoClass>,
     pub disabled: StyleSet<bool>,
@@ -317,6 +318,8 @@ pub struct Style {
 
     // Focus Order
     pub focus_order: SparseSet<FocusOrder>,
+    pub tab_index: SparseSet<i32>,
+    pub focus_scope: SparseSet<bool>,
 
     // Background
     pub background_color: AnimatableSet<Color>,
@@ -1512,6 +1515,7 @@ impl Style {
 
     pub fn remove(&mut self, entity: Entity) {
         self.elements.remove(entity);
+        self.ids.remove(entity);
         self.classes.remove(entity);
         self.pseudo_classes.remove(entity);
 
@@ -1525,6 +1529,10 @@ impl Style {
         self.z_order.remove(entity);
         // Clipping
         self.clip_widget.remove(entity);
+        // Focus
+        self.focus_order.remove(entity);
+        self.tab_index.remove(entity);
+        self.focus_scope.remove(entity);
 
         // Transform
         self.translate.remove(entity);
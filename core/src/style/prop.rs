@@ -296,10 +296,17 @@ pub trait PropSet: AsEntity + Sized {
 
     // Style
 
-    // TODO
-    fn set_id(self, _: &mut Context, _: &str) -> Entity {
-        todo!();
-        //self.entity()
+    /// Sets the id of an entity.
+    ///
+    /// An id can be referred to in css selectors, for example `#save`.
+    fn set_id(self, cx: &mut Context, id: &str) -> Entity {
+        cx.style.ids.insert(self.entity(), id.to_owned()).expect("Failed to insert id");
+
+        cx.style.needs_restyle = true;
+        cx.style.needs_relayout = true;
+        cx.style.needs_redraw = true;
+
+        self.entity()
     }
 
     /// Sets the visibility of an entity.
@@ -10,7 +10,7 @@ use bitflags::bitflags;
 
 bitflags! {
     /// A bitflag of possible pseudoclasses.
-    pub struct PseudoClass: u8 {
+    pub struct PseudoClass: u16 {
         const HOVER = 1;
         const OVER = 1 << 1;
         const ACTIVE = 1 << 2;
@@ -19,6 +19,7 @@ bitflags! {
         const CHECKED = 1 << 5;
         const SELECTED = 1 << 6;
         const CUSTOM = 1 << 7;
+        const DRAG_OVER = 1 << 8;
     }
 }
 
@@ -51,6 +52,9 @@ impl std::fmt::Display for PseudoClass {
         if self.contains(PseudoClass::SELECTED) {
             write!(f, ":selected")?;
         }
+        if self.contains(PseudoClass::DRAG_OVER) {
+            write!(f, ":drag-over")?;
+        }
 
         Ok(())
     }
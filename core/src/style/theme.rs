@@ -177,6 +177,13 @@ impl<'i> cssparser::AtRuleParser<'i> for RuleParser {
     */
 }
 
+/// Parses a selector string, such as `button.outlined` or `#save`, into a list of selectors.
+pub(crate) fn parse_selector_str(input: &str) -> Result<Vec<Selector>, String> {
+    let mut parser_input = ParserInput::new(input.trim());
+    let mut parser = Parser::new(&mut parser_input);
+    parse_selectors(&mut parser).map_err(|error| StyleParseError(error).to_string())
+}
+
 fn parse_selectors<'i, 't>(
     input: &mut Parser<'i, 't>,
 ) -> Result<Vec<Selector>, ParseError<'i, CustomParseError>> {
@@ -267,6 +274,7 @@ fn parse_selectors<'i, 't>(
                     "checked" => selector.pseudo_classes.insert(PseudoClass::CHECKED),
                     "selected" => selector.pseudo_classes.insert(PseudoClass::SELECTED),
                     "custom" => selector.pseudo_classes.insert(PseudoClass::CUSTOM),
+                    "drag-over" => selector.pseudo_classes.insert(PseudoClass::DRAG_OVER),
 
                     _ => {
                         let parse_error = ParseError {
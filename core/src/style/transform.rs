@@ -117,7 +117,12 @@ pub fn apply_transform(cx: &mut Context, tree: &Tree) {
 
         let parent = tree.get_parent(entity).unwrap();
         //let parent_origin = state.data.get_origin(parent);
-        let parent_transform = cx.cache.get_transform(parent);
+        // Portals are laid out in window coordinates, so they are not transformed by their parent
+        let parent_transform = if cx.portals.is_portal(entity) {
+            Transform2D::identity()
+        } else {
+            cx.cache.get_transform(parent)
+        };
 
         cx.cache.set_transform(entity, Transform2D::identity());
 
@@ -2,7 +2,7 @@ use femtovg::{Align, Baseline, Paint};
 use morphorm::Units;
 
 use crate::{
-    style::{Overflow, PropGet, Selector, SelectorRelation},
+    style::{parse_selector_str, Overflow, PropGet, Selector, SelectorRelation},
     BoundingBox, Context, Display, Entity, FontOrId, PseudoClass, Rule, Tree, TreeExt, Visibility,
 };
 
@@ -32,7 +32,14 @@ pub fn apply_clipping(cx: &mut Context, tree: &Tree) {
 
         let parent = tree.get_parent(entity).unwrap();
 
-        let parent_clip_region = cx.cache.get_clip_region(parent);
+        let root_clip_region = cx.cache.get_clip_region(Entity::root());
+
+        // Portals are not clipped by the views which contain them
+        let parent_clip_region = if cx.portals.is_portal(entity) {
+            root_clip_region
+        } else {
+            cx.cache.get_clip_region(parent)
+        };
         //let parent_border_width = cx.style.border_width.get(parent).cloned().unwrap_or_default().value_or(0.0, 0.0);
 
         //println!("Parent border width: {}", parent_border_width);
@@ -41,8 +48,6 @@ pub fn apply_clipping(cx: &mut Context, tree: &Tree) {
         // parent_clip_region.w;
         // parent_clip_region.h;
 
-        let root_clip_region = cx.cache.get_clip_region(Entity::root());
-
         let overflow = cx.style.overflow.get(entity).cloned().unwrap_or_default();
 
         if overflow == Overflow::Hidden {
@@ -346,10 +351,16 @@ fn check_match(cx: &Context, entity: Entity, selector: &Selector) -> bool {
         }
     }
 
-    // Check for ID match TODO
-    // if selector.id.is_some() && selector.id != entity_selector.id {
-    //     return false;
-    // }
+    // Check for ID match
+    if selector.id.is_some() {
+        if let Some(id) = cx.style.ids.get(entity) {
+            if selector.id != Selector::default().set_id(id).id {
+                return false;
+            }
+        } else {
+            return false;
+        }
+    }
 
     // Check for element name match
     if let Some(selector_element) = &selector.element {
@@ -395,6 +406,75 @@ fn check_match(cx: &Context, entity: Entity, selector: &Selector) -> bool {
     return true;
 }
 
+/// Returns true if the entity matches a list of selectors, such as those of a style rule.
+///
+/// The selectors are checked from right to left, with the relation of each selector determining
+/// whether it is checked against the entity itself, its parent, or one of its ancestors.
+pub fn match_selectors(cx: &Context, tree: &Tree, entity: Entity, selectors: &[Selector]) -> bool {
+    let mut relation_entity = entity;
+    // Loop through selectors (Should be from right to left)
+    // All the selectors need to match for the rule to apply
+    'selector_loop: for rule_selector in selectors.iter().rev() {
+        // Get the relation of the selector
+        match rule_selector.relation {
+            SelectorRelation::None => {
+                if !check_match(cx, entity, rule_selector) {
+                    return false;
+                }
+            }
+
+            SelectorRelation::Parent => {
+                // Get the parent
+                // Contrust the selector for the parent
+                // Check if the parent selector matches the rule_seletor
+                if let Some(parent) = relation_entity.parent(tree) {
+                    if !check_match(cx, parent, rule_selector) {
+                        return false;
+                    }
+
+                    relation_entity = parent;
+                } else {
+                    return false;
+                }
+            }
+
+            SelectorRelation::Ancestor => {
+                // Walk up the tree
+                // Check if each entity matches the selector
+                // If any of them match, move on to the next selector
+                // If none of them do, the selectors don't match
+                for ancestor in relation_entity.parent_iter(tree) {
+                    if ancestor == relation_entity {
+                        continue;
+                    }
+
+                    if check_match(cx, ancestor, rule_selector) {
+                        relation_entity = ancestor;
+
+                        continue 'selector_loop;
+                    }
+                }
+
+                return false;
+            }
+        }
+    }
+
+    true
+}
+
+/// Returns the entities which match a selector string, such as `button.outlined` or `#save`, in tree order.
+pub fn query_selector(cx: &Context, selector: &str) -> Result<Vec<Entity>, String> {
+    let selectors = parse_selector_str(selector)?;
+
+    Ok(cx
+        .tree
+        .into_iter()
+        .filter(|entity| *entity != Entity::root())
+        .filter(|entity| match_selectors(cx, &cx.tree, *entity, &selectors))
+        .collect())
+}
+
 pub fn apply_styles(cx: &mut Context, tree: &Tree) {
     //println!("RESTYLE");
     // Loop through all entities
@@ -408,58 +488,11 @@ pub fn apply_styles(cx: &mut Context, tree: &Tree) {
         let mut matched_rules: Vec<Rule> = Vec::new();
 
         // Loop through all of the style rules
-        'rule_loop: for rule in cx.style.rules.iter() {
-            let mut relation_entity = entity;
-            // Loop through selectors (Should be from right to left)
-            // All the selectors need to match for the rule to apply
-            'selector_loop: for rule_selector in rule.selectors.iter().rev() {
-                // Get the relation of the selector
-                match rule_selector.relation {
-                    SelectorRelation::None => {
-                        if !check_match(cx, entity, rule_selector) {
-                            continue 'rule_loop;
-                        }
-                    }
-
-                    SelectorRelation::Parent => {
-                        // Get the parent
-                        // Contrust the selector for the parent
-                        // Check if the parent selector matches the rule_seletor
-                        if let Some(parent) = relation_entity.parent(tree) {
-                            if !check_match(cx, parent, rule_selector) {
-                                continue 'rule_loop;
-                            }
-
-                            relation_entity = parent;
-                        } else {
-                            continue 'rule_loop;
-                        }
-                    }
-
-                    SelectorRelation::Ancestor => {
-                        // Walk up the tree
-                        // Check if each entity matches the selector
-                        // If any of them match, move on to the next selector
-                        // If none of them do, move on to the next rule
-                        for ancestor in relation_entity.parent_iter(tree) {
-                            if ancestor == relation_entity {
-                                continue;
-                            }
-
-                            if check_match(cx, ancestor, rule_selector) {
-                                relation_entity = ancestor;
-
-                                continue 'selector_loop;
-                            }
-                        }
-
-                        continue 'rule_loop;
-                    }
-                }
-            }
-
+        for rule in cx.style.rules.iter() {
             // If all the selectors match then add the rule to the matched rules list
-            matched_rules.push(rule.id);
+            if match_selectors(cx, tree, entity, &rule.selectors) {
+                matched_rules.push(rule.id);
+            }
         }
 
         //println!("Entity: {}, Matched Rules: {:?}", entity, &matched_rules);
@@ -0,0 +1,390 @@
+use std::future::Future;
+use std::panic::{self, AssertUnwindSafe};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context as TaskContext, Wake, Waker};
+use std::thread;
+
+use crate::Entity;
+
+#[derive(Default)]
+struct TaskState {
+    cancelled: AtomicBool,
+    finished: AtomicBool,
+    // Wakes the future of the task when it is cancelled, so that the future is dropped
+    waker: Mutex<Option<Waker>>,
+}
+
+/// A handle to a task which was spawned with `Context::spawn` or `Context::spawn_future`.
+#[derive(Clone)]
+pub struct TaskHandle {
+    state: Arc<TaskState>,
+}
+
+impl TaskHandle {
+    fn new() -> Self {
+        Self { state: Arc::new(TaskState::default()) }
+    }
+
+    /// Cancels the task.
+    ///
+    /// The result of a cancelled task is discarded instead of being sent as an event. A future
+    /// stops being polled once its task is cancelled, while a closure runs to completion.
+    pub fn cancel(&self) {
+        self.state.cancelled.store(true, Ordering::Release);
+
+        let waker = self.state.waker.lock().expect("Failed to lock task").take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Returns true if the task has completed or has been stopped after being cancelled.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+}
+
+// The maximum number of worker threads which run tasks
+const MAX_WORKERS: usize = 4;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Storage for the handles of the pending tasks of all entities.
+///
+/// Tasks are run on a pool of up to four worker threads, which is started when the first task is
+/// spawned. Tasks which are spawned while all of the workers are busy wait in a queue until a
+/// worker is free. A future only occupies a worker while it is being polled, and is queued to be
+/// polled again whenever it is woken.
+#[derive(Default)]
+pub struct Tasks {
+    tasks: Vec<(Entity, TaskHandle)>,
+    // Sends jobs to the worker threads, which exit once every sender has been dropped
+    jobs: Option<Sender<Job>>,
+}
+
+impl Tasks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Creates a handle for a new task of an entity
+    pub(crate) fn add(&mut self, entity: Entity) -> TaskHandle {
+        // Forget about tasks which have already finished
+        self.tasks.retain(|(_, task)| !task.is_finished());
+
+        let task = TaskHandle::new();
+        self.tasks.push((entity, task.clone()));
+
+        task
+    }
+
+    /// Cancels all of the pending tasks of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        for (_, task) in self.tasks.iter().filter(|(task_entity, _)| *task_entity == entity) {
+            task.cancel();
+        }
+
+        self.tasks.retain(|(task_entity, _)| *task_entity != entity);
+    }
+
+    // Runs a closure on a worker thread, passing the result to `on_complete` unless the task was
+    // cancelled
+    pub(crate) fn run_task<T, F, C>(&mut self, task: TaskHandle, f: F, on_complete: C)
+    where
+        T: Send + 'static,
+        F: 'static + Send + FnOnce() -> T,
+        C: 'static + Send + FnOnce(T),
+    {
+        self.execute(task, move |task| {
+            let result = (f)();
+
+            if !task.is_cancelled() {
+                (on_complete)(result);
+            }
+        });
+    }
+
+    // Polls a future to completion on the worker threads, passing the output to `on_complete`
+    // unless the task was cancelled
+    pub(crate) fn run_future<Fut, C>(&mut self, task: TaskHandle, future: Fut, on_complete: C)
+    where
+        Fut: 'static + Send + Future,
+        C: 'static + Send + FnOnce(Fut::Output),
+    {
+        let handle = task.clone();
+        let future = async move {
+            let output = future.await;
+
+            if !handle.is_cancelled() {
+                (on_complete)(output);
+            }
+        };
+
+        let job = Arc::new(FutureJob {
+            task,
+            future: Mutex::new(Some(Box::pin(future))),
+            jobs: Mutex::new(self.jobs().clone()),
+            queued: AtomicBool::new(false),
+        });
+
+        *job.task.state.waker.lock().expect("Failed to lock task") = Some(Waker::from(job.clone()));
+
+        job.schedule();
+    }
+
+    // Queues a job for the worker threads, marking its task as finished once the job returns
+    fn execute<F>(&mut self, task: TaskHandle, job: F)
+    where
+        F: 'static + Send + FnOnce(&TaskHandle),
+    {
+        let job: Job = Box::new(move || {
+            // A panicking task must not bring down its worker thread
+            panic::catch_unwind(AssertUnwindSafe(|| (job)(&task))).ok();
+            task.state.finished.store(true, Ordering::Release);
+        });
+
+        self.jobs().send(job).expect("Failed to send task to worker");
+    }
+
+    // Returns the sender which queues jobs for the worker threads, starting the workers if needed
+    fn jobs(&mut self) -> &Sender<Job> {
+        self.jobs.get_or_insert_with(start_workers)
+    }
+}
+
+// Starts the worker threads, returning the sender which is used to queue jobs for them
+fn start_workers() -> Sender<Job> {
+    let (sender, receiver) = mpsc::channel::<Job>();
+    let receiver = Arc::new(Mutex::new(receiver));
+
+    let workers =
+        thread::available_parallelism().map(|count| count.get()).unwrap_or(1).min(MAX_WORKERS);
+
+    for _ in 0..workers {
+        let receiver = receiver.clone();
+
+        thread::spawn(move || loop {
+            // The lock is released before the job is run, so that other workers can take jobs
+            let job = match receiver.lock().expect("Failed to lock job queue").recv() {
+                Ok(job) => job,
+                Err(_) => break,
+            };
+
+            (job)();
+        });
+    }
+
+    sender
+}
+
+// A future which is polled on the worker threads each time it is woken
+struct FutureJob {
+    task: TaskHandle,
+    // The future is dropped once it completes, panics or is cancelled
+    future: Mutex<Option<Pin<Box<dyn Future<Output = ()> + Send>>>>,
+    jobs: Mutex<Sender<Job>>,
+    // Set while a poll of the future is waiting in the job queue
+    queued: AtomicBool,
+}
+
+impl FutureJob {
+    // Queues a poll of the future, unless one is already queued
+    fn schedule(self: Arc<Self>) {
+        if !self.queued.swap(true, Ordering::AcqRel) {
+            let jobs = self.jobs.lock().expect("Failed to lock job queue").clone();
+            jobs.send(Box::new(move || self.poll())).expect("Failed to send task to worker");
+        }
+    }
+
+    // Polls the future once, dropping it if the task has been cancelled
+    fn poll(self: Arc<Self>) {
+        self.queued.store(false, Ordering::Release);
+
+        let mut future = self.future.lock().expect("Failed to lock future");
+
+        let finished = match future.as_mut() {
+            Some(_) if self.task.is_cancelled() => true,
+
+            Some(pending) => {
+                let waker = Waker::from(self.clone());
+                let mut cx = TaskContext::from_waker(&waker);
+
+                // A panicking future must not bring down its worker thread
+                panic::catch_unwind(AssertUnwindSafe(|| pending.as_mut().poll(&mut cx)))
+                    .map_or(true, |poll| poll.is_ready())
+            }
+
+            None => false,
+        };
+
+        if finished {
+            *future = None;
+            self.task.state.waker.lock().expect("Failed to lock task").take();
+            self.task.state.finished.store(true, Ordering::Release);
+        }
+    }
+}
+
+impl Wake for FutureJob {
+    fn wake(self: Arc<Self>) {
+        self.schedule();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{mpsc, Arc, Mutex};
+    use std::task::{Context as TaskContext, Poll, Waker};
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::{Context, Entity, Event};
+
+    #[derive(Debug, PartialEq)]
+    struct Done(usize);
+
+    // Waits for the next event sent to the context from another thread
+    fn next_event(cx: &Context, timeout: Duration) -> Option<Event> {
+        cx.proxy_receiver.recv_timeout(timeout).ok()
+    }
+
+    #[test]
+    fn task_result_is_sent_to_the_current_entity() {
+        let mut cx = Context::new();
+
+        cx.spawn(|| Done(1));
+
+        let mut event = next_event(&cx, Duration::from_secs(5)).expect("Task did not complete");
+        assert_eq!(event.target, Entity::root());
+        assert_eq!(event.message.downcast::<Done>(), Some(&mut Done(1)));
+    }
+
+    #[test]
+    fn cancelled_task_result_is_discarded() {
+        let mut cx = Context::new();
+        let (sender, receiver) = mpsc::channel::<()>();
+
+        let task = cx.spawn(move || {
+            receiver.recv().ok();
+            Done(1)
+        });
+
+        cx.tasks.remove_entity(Entity::root());
+        assert!(task.is_cancelled());
+
+        sender.send(()).unwrap();
+
+        assert!(next_event(&cx, Duration::from_millis(200)).is_none());
+    }
+
+    #[test]
+    fn tasks_wait_for_a_free_worker() {
+        let mut cx = Context::new();
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        for index in 0..4 * super::MAX_WORKERS {
+            let running = running.clone();
+            let max_running = max_running.clone();
+
+            cx.spawn(move || {
+                let count = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(count, Ordering::SeqCst);
+                thread::sleep(Duration::from_millis(10));
+                running.fetch_sub(1, Ordering::SeqCst);
+
+                Done(index)
+            });
+        }
+
+        let mut results = (0..4 * super::MAX_WORKERS)
+            .map(|_| next_event(&cx, Duration::from_secs(5)).expect("Task did not complete"))
+            .map(|mut event| event.message.downcast::<Done>().map(|done| done.0).unwrap())
+            .collect::<Vec<_>>();
+        results.sort();
+
+        assert_eq!(results, (0..4 * super::MAX_WORKERS).collect::<Vec<_>>());
+        assert!(max_running.load(Ordering::SeqCst) <= super::MAX_WORKERS);
+    }
+
+    // A future which is pending until the signal is set
+    #[derive(Clone, Default)]
+    struct Signal(Arc<Mutex<(bool, Vec<Waker>)>>);
+
+    impl Signal {
+        fn set(&self) {
+            let mut state = self.0.lock().unwrap();
+            state.0 = true;
+            state.1.drain(..).for_each(Waker::wake);
+        }
+    }
+
+    impl Future for Signal {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut TaskContext) -> Poll<()> {
+            let mut state = self.0.lock().unwrap();
+
+            if state.0 {
+                Poll::Ready(())
+            } else {
+                state.1.push(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn pending_futures_do_not_occupy_workers() {
+        let mut cx = Context::new();
+        let signal = Signal::default();
+
+        for index in 0..4 * super::MAX_WORKERS {
+            let signal = signal.clone();
+            cx.spawn_future(async move {
+                signal.await;
+                Done(index)
+            });
+        }
+
+        // Every worker would be blocked if pending futures held on to them
+        cx.spawn(|| Done(100));
+
+        let mut event = next_event(&cx, Duration::from_secs(5)).expect("Task did not complete");
+        assert_eq!(event.message.downcast::<Done>(), Some(&mut Done(100)));
+
+        signal.set();
+
+        for _ in 0..4 * super::MAX_WORKERS {
+            next_event(&cx, Duration::from_secs(5)).expect("Future did not complete");
+        }
+    }
+
+    #[test]
+    fn cancelled_future_is_dropped() {
+        let mut cx = Context::new();
+        let signal = Signal::default();
+
+        let task = cx.spawn_future(signal.clone());
+        cx.tasks.remove_entity(Entity::root());
+
+        let start = Instant::now();
+        while !task.is_finished() {
+            assert!(start.elapsed() < Duration::from_secs(5), "Future was not dropped");
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        // The signal is only referenced by the test once the future has been dropped
+        assert_eq!(Arc::strong_count(&signal.0), 1);
+    }
+}
@@ -0,0 +1,173 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::{Entity, Event};
+
+/// The shortest interval of a repeating timer.
+pub const MIN_REPEAT_INTERVAL: Duration = Duration::from_millis(1);
+
+/// An identifier for a timer, returned by `Context::add_timer` and `Context::emit_after`.
+///
+/// The identifier can be passed to `Context::cancel_timer` to stop the timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimerId(u64);
+
+struct Timer {
+    id: TimerId,
+    entity: Entity,
+    interval: Duration,
+    repeat: bool,
+    due: Instant,
+    // Creates the event to send when the timer is due, or None if there is nothing left to send
+    event: Box<dyn FnMut() -> Option<Event>>,
+}
+
+/// Storage for the timers of all entities.
+///
+/// Timers are checked at the start of every frame, and the events of any timers which are due
+/// are pushed into the event queue. Backends use [Timers::next_due] to decide when to wake up.
+#[derive(Default)]
+pub struct Timers {
+    timers: Vec<Timer>,
+    next_id: u64,
+}
+
+impl Timers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a timer for an entity and returns its id.
+    ///
+    /// Repeating timers fire at most once every [MIN_REPEAT_INTERVAL], so that a zero interval
+    /// cannot keep the event loop busy.
+    pub(crate) fn add(
+        &mut self,
+        entity: Entity,
+        interval: Duration,
+        repeat: bool,
+        event: Box<dyn FnMut() -> Option<Event>>,
+    ) -> TimerId {
+        let interval = if repeat { interval.max(MIN_REPEAT_INTERVAL) } else { interval };
+
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+
+        self.timers.push(Timer {
+            id,
+            entity,
+            interval,
+            repeat,
+            due: Instant::now() + interval,
+            event,
+        });
+
+        id
+    }
+
+    /// Cancels a timer, returning true if the timer was still running.
+    pub fn cancel(&mut self, id: TimerId) -> bool {
+        let len = self.timers.len();
+        self.timers.retain(|timer| timer.id != id);
+        self.timers.len() != len
+    }
+
+    /// Cancels all of the timers of an entity.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.timers.retain(|timer| timer.entity != entity);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Returns the time at which the next timer is due, if there are any timers.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.timers.iter().map(|timer| timer.due).min()
+    }
+
+    /// Pushes the events of all timers which are due at `now` into the event queue.
+    ///
+    /// Repeating timers are rescheduled, while other timers are removed. A repeating timer which
+    /// has missed several intervals only sends one event.
+    pub fn tick(&mut self, now: Instant, event_queue: &mut VecDeque<Event>) {
+        // Send the events of timers which were due earliest first
+        self.timers.sort_by_key(|timer| timer.due);
+
+        let mut finished = Vec::new();
+
+        for timer in self.timers.iter_mut().filter(|timer| timer.due <= now) {
+            if let Some(event) = (timer.event)() {
+                event_queue.push_back(event);
+            }
+
+            if timer.repeat {
+                timer.due += timer.interval;
+
+                if timer.due <= now {
+                    timer.due = now + timer.interval;
+                }
+            } else {
+                finished.push(timer.id);
+            }
+        }
+
+        self.timers.retain(|timer| !finished.contains(&timer.id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> Box<dyn FnMut() -> Option<Event>> {
+        Box::new(|| Some(Event::new(())))
+    }
+
+    #[test]
+    fn one_shot_timer_is_removed() {
+        let mut timers = Timers::new();
+        let mut event_queue = VecDeque::new();
+        let start = Instant::now();
+
+        timers.add(Entity::root(), Duration::from_millis(100), false, event());
+
+        timers.tick(start, &mut event_queue);
+        assert!(event_queue.is_empty());
+
+        timers.tick(start + Duration::from_millis(200), &mut event_queue);
+        assert_eq!(event_queue.len(), 1);
+        assert!(timers.is_empty());
+    }
+
+    #[test]
+    fn repeating_timer_is_rescheduled() {
+        let mut timers = Timers::new();
+        let mut event_queue = VecDeque::new();
+        let start = Instant::now();
+
+        let id = timers.add(Entity::root(), Duration::from_millis(100), true, event());
+
+        timers.tick(start + Duration::from_millis(150), &mut event_queue);
+        timers.tick(start + Duration::from_millis(250), &mut event_queue);
+        assert_eq!(event_queue.len(), 2);
+        assert!(timers.next_due().unwrap() > start + Duration::from_millis(250));
+
+        assert!(timers.cancel(id));
+        assert!(timers.next_due().is_none());
+    }
+
+    #[test]
+    fn zero_repeat_interval_is_clamped() {
+        let mut timers = Timers::new();
+        let mut event_queue = VecDeque::new();
+        let start = Instant::now();
+
+        timers.add(Entity::root(), Duration::ZERO, true, event());
+
+        let now = start + Duration::from_millis(10);
+        timers.tick(now, &mut event_queue);
+        assert_eq!(event_queue.len(), 1);
+        assert!(timers.next_due().unwrap() >= now + MIN_REPEAT_INTERVAL);
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use morphorm::Units;
+
+use crate::{Abilities, Context, Entity, Label, Tooltip, TreeExt};
+
+// The offset of a tooltip from the cursor, so that the cursor does not cover it
+const TOOLTIP_OFFSET: (f32, f32) = (0.0, 20.0);
+
+/// Storage for the tooltip content of all entities, and the state of the shown tooltip.
+///
+/// A tooltip is shown once the cursor has rested on a view with a tooltip for the hover delay,
+/// and is hidden when the cursor leaves the view or a mouse button is pressed.
+pub struct TooltipState {
+    /// The time which the cursor must rest on a view before its tooltip is shown.
+    pub delay: Duration,
+    builders: HashMap<Entity, Box<dyn Fn(&mut Context)>>,
+    // The view with a tooltip which is under the cursor
+    owner: Entity,
+    // The time at which the tooltip of the owner is shown, if it is not shown yet
+    due: Option<Instant>,
+    // The shown tooltip
+    overlay: Entity,
+    // Shown tooltips whose views have been removed, which are removed along with them
+    orphaned: Vec<Entity>,
+}
+
+impl Default for TooltipState {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_millis(500),
+            builders: HashMap::new(),
+            owner: Entity::null(),
+            due: None,
+            overlay: Entity::null(),
+            orphaned: Vec::new(),
+        }
+    }
+}
+
+impl TooltipState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Sets the content of the tooltip of an entity
+    pub(crate) fn set_content(&mut self, entity: Entity, content: Box<dyn Fn(&mut Context)>) {
+        self.builders.insert(entity, content);
+    }
+
+    /// Removes the tooltip content of an entity.
+    ///
+    /// A tooltip which is shown for the entity is removed by `Context::remove` along with it.
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.builders.remove(&entity);
+
+        if self.owner == entity {
+            self.owner = Entity::null();
+            self.due = None;
+
+            if self.overlay != Entity::null() {
+                self.orphaned.push(self.overlay);
+                self.overlay = Entity::null();
+            }
+        }
+
+        if self.overlay == entity {
+            self.overlay = Entity::null();
+        }
+
+        self.orphaned.retain(|overlay| *overlay != entity);
+    }
+
+    // Takes the tooltips which were shown for removed entities
+    pub(crate) fn take_orphaned(&mut self) -> Vec<Entity> {
+        std::mem::take(&mut self.orphaned)
+    }
+
+    /// Returns the time at which the pending tooltip is shown, if there is one.
+    pub fn next_due(&self) -> Option<Instant> {
+        self.due
+    }
+
+    /// Returns the shown tooltip, if there is one.
+    pub fn overlay(&self) -> Option<Entity> {
+        if self.overlay != Entity::null() {
+            Some(self.overlay)
+        } else {
+            None
+        }
+    }
+}
+
+/// Updates the tooltip after the cursor has moved.
+///
+/// If the cursor has moved onto a different view with a tooltip, the shown tooltip is hidden and
+/// the tooltip of the new view is shown after the hover delay.
+pub fn tooltip_move(cx: &mut Context) {
+    let owner = cx
+        .hovered
+        .parent_iter(&cx.tree)
+        .find(|ancestor| has_tooltip(cx, *ancestor))
+        .unwrap_or(Entity::null());
+
+    if owner == cx.tooltip.owner {
+        return;
+    }
+
+    hide_tooltip(cx);
+
+    cx.tooltip.owner = owner;
+    cx.tooltip.due =
+        if owner != Entity::null() { Some(Instant::now() + cx.tooltip.delay) } else { None };
+}
+
+/// Hides the tooltip when a mouse button is pressed.
+///
+/// The tooltip is not shown again until the cursor moves onto a different view.
+pub fn tooltip_press(cx: &mut Context) {
+    hide_tooltip(cx);
+
+    cx.tooltip.due = None;
+}
+
+/// Shows the pending tooltip if it is due at `now`.
+pub fn tooltip_tick(cx: &mut Context, now: Instant) {
+    match cx.tooltip.due {
+        Some(due) if due <= now => {}
+        _ => return,
+    }
+
+    cx.tooltip.due = None;
+
+    let owner = cx.tooltip.owner;
+    let content = cx.tooltip.builders.remove(&owner);
+    let text = cx.style.tooltip.get(owner).cloned();
+
+    let x = cx.mouse.cursorx + TOOLTIP_OFFSET.0;
+    let y = cx.mouse.cursory + TOOLTIP_OFFSET.1;
+
+    // The tooltip is built as a child of the root so that it is not clipped by its view
+    let overlay = cx.with_current(Entity::root(), |cx| {
+        Tooltip::new(cx, |_| {}).left(Units::Pixels(x)).top(Units::Pixels(y)).entity
+    });
+
+    cx.with_current(overlay, |cx| {
+        if let Some(content) = &content {
+            (content)(cx);
+        } else if let Some(text) = text {
+            Label::new(cx, text.as_str());
+        }
+    });
+
+    if let Some(content) = content {
+        cx.tooltip.builders.insert(owner, content);
+    }
+
+    // The tooltip must not take the hover away from its view
+    for entity in overlay.branch_iter(&cx.tree).collect::<Vec<_>>() {
+        if let Some(abilities) = cx.style.abilities.get_mut(entity) {
+            abilities.set(Abilities::HOVERABLE, false);
+        }
+    }
+
+    cx.tooltip.overlay = overlay;
+}
+
+fn hide_tooltip(cx: &mut Context) {
+    if cx.tooltip.overlay != Entity::null() {
+        let overlay = cx.tooltip.overlay;
+        cx.remove(overlay);
+        cx.tooltip.overlay = Entity::null();
+    }
+}
+
+// Returns true if the entity has tooltip text or content
+fn has_tooltip(cx: &Context, entity: Entity) -> bool {
+    cx.style.tooltip.get(entity).is_some() || cx.tooltip.builders.contains_key(&entity)
+}
@@ -283,7 +283,7 @@ impl Tree {
 
         // Check if sibling has the same parent
         if let Some(parent) = self.get_parent(entity) {
-            if let Some(sibling_parent) = self.get_parent(entity) {
+            if let Some(sibling_parent) = self.get_parent(sibling) {
                 if parent != sibling_parent {
                     return Err(TreeError::InvalidSibling);
                 }
@@ -338,7 +338,7 @@ impl Tree {
 
         // Check if sibling has the same parent
         if let Some(parent) = self.get_parent(entity) {
-            if let Some(sibling_parent) = self.get_parent(entity) {
+            if let Some(sibling_parent) = self.get_parent(sibling) {
                 if parent != sibling_parent {
                     return Err(TreeError::InvalidSibling);
                 }
@@ -470,6 +470,194 @@ impl Tree {
 
         Ok(())
     }
+
+    /// Inserts an entity as the previous sibling of another entity.
+    ///
+    /// The entity can be new to the tree, or it can already be in the tree, in which case it is moved
+    /// along with its descendants.
+    pub fn insert_before(&mut self, entity: Entity, sibling: Entity) -> Result<(), TreeError> {
+        let parent = self.check_insert(entity, sibling)?;
+
+        self.detach(entity);
+        let prev_sibling = self.get_prev_sibling(sibling);
+        self.attach(entity, parent, prev_sibling);
+
+        Ok(())
+    }
+
+    /// Inserts an entity as the next sibling of another entity.
+    ///
+    /// The entity can be new to the tree, or it can already be in the tree, in which case it is moved
+    /// along with its descendants.
+    pub fn insert_after(&mut self, entity: Entity, sibling: Entity) -> Result<(), TreeError> {
+        let parent = self.check_insert(entity, sibling)?;
+
+        self.detach(entity);
+        self.attach(entity, parent, Some(sibling));
+
+        Ok(())
+    }
+
+    /// Moves an entity, along with its descendants, to be the child of a parent at an index.
+    ///
+    /// The index is clamped to the number of children of the parent, so an index past the end moves
+    /// the entity to be the last child.
+    pub fn move_to(
+        &mut self,
+        entity: Entity,
+        parent: Entity,
+        index: usize,
+    ) -> Result<(), TreeError> {
+        if entity == Entity::null() || parent == Entity::null() {
+            return Err(TreeError::NullEntity);
+        }
+
+        if self.get_parent(entity).is_none() {
+            return Err(TreeError::NoEntity);
+        }
+
+        if parent.index() >= self.parent.len() || self.is_ancestor(entity, parent) {
+            return Err(TreeError::InvalidParent);
+        }
+
+        self.detach(entity);
+
+        let prev_sibling = if index == 0 {
+            None
+        } else {
+            self.get_child(parent, index - 1).or_else(|| self.get_last_child(parent))
+        };
+
+        self.attach(entity, parent, prev_sibling);
+
+        Ok(())
+    }
+
+    /// Swaps the positions of two entities, along with their descendants.
+    ///
+    /// The entities can have different parents, but one cannot be the ancestor of the other.
+    pub fn swap(&mut self, entity1: Entity, entity2: Entity) -> Result<(), TreeError> {
+        if entity1 == Entity::null() || entity2 == Entity::null() {
+            return Err(TreeError::NullEntity);
+        }
+
+        if entity1 == entity2 {
+            return Ok(());
+        }
+
+        let (parent1, parent2) = match (self.get_parent(entity1), self.get_parent(entity2)) {
+            (Some(parent1), Some(parent2)) => (parent1, parent2),
+            _ => return Err(TreeError::NoEntity),
+        };
+
+        if self.is_ancestor(entity1, entity2) || self.is_ancestor(entity2, entity1) {
+            return Err(TreeError::InvalidParent);
+        }
+
+        let prev_sibling1 = self.get_prev_sibling(entity1);
+        let prev_sibling2 = self.get_prev_sibling(entity2);
+
+        if prev_sibling2 == Some(entity1) {
+            // from:    [E1] -> [E2]
+            // to:      [E2] -> [E1]
+            self.detach(entity1);
+            self.attach(entity1, parent2, Some(entity2));
+        } else if prev_sibling1 == Some(entity2) {
+            self.detach(entity2);
+            self.attach(entity2, parent1, Some(entity1));
+        } else {
+            // Neither entity is the previous sibling of the other, so the previous siblings stay in place
+            self.detach(entity1);
+            self.detach(entity2);
+            self.attach(entity1, parent2, prev_sibling2);
+            self.attach(entity2, parent1, prev_sibling1);
+        }
+
+        Ok(())
+    }
+
+    // Checks that an entity can be inserted next to a sibling and returns the parent of the sibling
+    fn check_insert(&mut self, entity: Entity, sibling: Entity) -> Result<Entity, TreeError> {
+        if entity == Entity::null() || sibling == Entity::null() {
+            return Err(TreeError::NullEntity);
+        }
+
+        if entity == sibling {
+            return Err(TreeError::InvalidSibling);
+        }
+
+        let parent = match self.get_parent(sibling) {
+            Some(parent) => parent,
+            None => return Err(TreeError::InvalidSibling),
+        };
+
+        if self.is_ancestor(entity, parent) {
+            return Err(TreeError::InvalidParent);
+        }
+
+        let entity_index = entity.index();
+
+        if entity_index >= self.parent.len() {
+            self.parent.resize(entity_index + 1, None);
+            self.first_child.resize(entity_index + 1, None);
+            self.next_sibling.resize(entity_index + 1, None);
+            self.prev_sibling.resize(entity_index + 1, None);
+        }
+
+        Ok(parent)
+    }
+
+    // Returns true if the ancestor is the entity or one of its ancestors
+    fn is_ancestor(&self, ancestor: Entity, entity: Entity) -> bool {
+        entity.parent_iter(self).any(|parent| parent == ancestor)
+    }
+
+    // Removes an entity from its parent and siblings, keeping its children
+    fn detach(&mut self, entity: Entity) {
+        let prev_sibling = self.get_prev_sibling(entity);
+        let next_sibling = self.get_next_sibling(entity);
+
+        if let Some(parent) = self.get_parent(entity) {
+            if self.first_child[parent.index()] == Some(entity) {
+                self.first_child[parent.index()] = next_sibling;
+            }
+        }
+
+        if let Some(prev_sibling) = prev_sibling {
+            self.next_sibling[prev_sibling.index()] = next_sibling;
+        }
+
+        if let Some(next_sibling) = next_sibling {
+            self.prev_sibling[next_sibling.index()] = prev_sibling;
+        }
+
+        self.parent[entity.index()] = None;
+        self.next_sibling[entity.index()] = None;
+        self.prev_sibling[entity.index()] = None;
+    }
+
+    // Adds a detached entity to a parent after the previous sibling, or as the first child if there is none
+    fn attach(&mut self, entity: Entity, parent: Entity, prev_sibling: Option<Entity>) {
+        let next_sibling = match prev_sibling {
+            Some(prev_sibling) => self.get_next_sibling(prev_sibling),
+            None => self.get_first_child(parent),
+        };
+
+        match prev_sibling {
+            Some(prev_sibling) => self.next_sibling[prev_sibling.index()] = Some(entity),
+            None => self.first_child[parent.index()] = Some(entity),
+        }
+
+        if let Some(next_sibling) = next_sibling {
+            self.prev_sibling[next_sibling.index()] = Some(entity);
+        }
+
+        self.parent[entity.index()] = Some(parent);
+        self.prev_sibling[entity.index()] = prev_sibling;
+        self.next_sibling[entity.index()] = next_sibling;
+
+        self.changed = true;
+    }
 }
 
 impl<'a> IntoIterator for &'a Tree {
@@ -480,3 +668,89 @@ impl<'a> IntoIterator for &'a Tree {
         TreeIterator { tree: self, current_node: Some(Entity::root()) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Creates a tree with the given number of children of the root
+    fn tree_with_children(count: u32) -> (Tree, Vec<Entity>) {
+        let mut tree = Tree::new();
+        let children = (1..=count).map(|index| Entity::new(index, 0)).collect::<Vec<_>>();
+        for child in children.iter() {
+            tree.add(*child, Entity::root()).unwrap();
+        }
+
+        (tree, children)
+    }
+
+    // Returns the children of an entity, checking that the sibling links agree in both directions
+    fn children(tree: &Tree, entity: Entity) -> Vec<Entity> {
+        let children = entity.child_iter(tree).collect::<Vec<_>>();
+
+        let mut prev = None;
+        for child in children.iter() {
+            assert_eq!(tree.get_parent(*child), Some(entity));
+            assert_eq!(tree.get_prev_sibling(*child), prev);
+            prev = Some(*child);
+        }
+
+        children
+    }
+
+    #[test]
+    fn insert_new_entities() {
+        let (mut tree, c) = tree_with_children(2);
+        let (first, middle) = (Entity::new(3, 0), Entity::new(4, 0));
+
+        tree.insert_before(first, c[0]).unwrap();
+        tree.insert_after(middle, c[0]).unwrap();
+
+        assert_eq!(children(&tree, Entity::root()), vec![first, c[0], middle, c[1]]);
+    }
+
+    #[test]
+    fn insert_moves_existing_entities() {
+        let (mut tree, c) = tree_with_children(4);
+
+        tree.insert_before(c[3], c[0]).unwrap();
+        assert_eq!(children(&tree, Entity::root()), vec![c[3], c[0], c[1], c[2]]);
+
+        tree.insert_after(c[3], c[2]).unwrap();
+        assert_eq!(children(&tree, Entity::root()), vec![c[0], c[1], c[2], c[3]]);
+
+        assert!(matches!(tree.insert_after(c[0], c[0]), Err(TreeError::InvalidSibling)));
+    }
+
+    #[test]
+    fn move_to_new_parent() {
+        let (mut tree, c) = tree_with_children(4);
+
+        tree.move_to(c[1], c[0], 0).unwrap();
+        tree.move_to(c[2], c[0], 10).unwrap();
+        tree.move_to(c[3], c[0], 1).unwrap();
+
+        assert_eq!(children(&tree, Entity::root()), vec![c[0]]);
+        assert_eq!(children(&tree, c[0]), vec![c[1], c[3], c[2]]);
+
+        // An entity cannot be moved into its own descendant
+        assert!(matches!(tree.move_to(c[0], c[1], 0), Err(TreeError::InvalidParent)));
+    }
+
+    #[test]
+    fn swap_entities() {
+        let (mut tree, c) = tree_with_children(4);
+
+        tree.swap(c[0], c[3]).unwrap();
+        assert_eq!(children(&tree, Entity::root()), vec![c[3], c[1], c[2], c[0]]);
+
+        tree.swap(c[1], c[2]).unwrap();
+        tree.swap(c[0], c[2]).unwrap();
+        assert_eq!(children(&tree, Entity::root()), vec![c[3], c[0], c[1], c[2]]);
+
+        tree.move_to(c[3], c[0], 0).unwrap();
+        tree.swap(c[3], c[1]).unwrap();
+        assert_eq!(children(&tree, Entity::root()), vec![c[0], c[3], c[2]]);
+        assert_eq!(children(&tree, c[0]), vec![c[1]]);
+    }
+}
@@ -3,6 +3,9 @@ use crate::{
     Context, Event, FontOrId, Handle, ViewHandler,
 };
 
+#[cfg(feature = "software")]
+use crate::SoftwareCanvas;
+
 use femtovg::{
     renderer::OpenGl, Align, Baseline, ImageFlags, Paint, Path, PixelFormat, RenderTarget,
 };
@@ -128,6 +131,13 @@ pub trait View: 'static + Sized {
     #[allow(unused_variables)]
     fn event(&mut self, cx: &mut Context, event: &mut Event) {}
 
+    /// Receives `Down` and `DownUp` events targeted at a descendant of the view, before the
+    /// descendants see them.
+    ///
+    /// Consuming the event here stops it from reaching the target.
+    #[allow(unused_variables)]
+    fn event_capture(&mut self, cx: &mut Context, event: &mut Event) {}
+
     fn draw(&self, cx: &mut Context, canvas: &mut Canvas) {
         //println!("{}", debug(&mut context, entity));
         let entity = cx.current;
@@ -728,6 +738,15 @@ pub trait View: 'static + Sized {
         //     canvas.fill_text(bounds.x + bounds.w / 2.0, bounds.y + bounds.h / 2.0, text, paint);
         // }
     }
+
+    /// Draws the view with the software renderer.
+    ///
+    /// Views with a custom `draw` should override this to render the same output into a
+    /// [SoftwareCanvas], otherwise the default visuals of the view are drawn.
+    #[cfg(feature = "software")]
+    fn draw_software(&self, cx: &mut Context, canvas: &mut SoftwareCanvas) {
+        crate::software::draw_view(cx, canvas);
+    }
 }
 
 impl<T: View> ViewHandler for T
@@ -746,7 +765,16 @@ where
         <T as View>::event(self, cx, event);
     }
 
+    fn event_capture(&mut self, cx: &mut Context, event: &mut Event) {
+        <T as View>::event_capture(self, cx, event);
+    }
+
     fn draw(&self, cx: &mut Context, canvas: &mut Canvas) {
         <T as View>::draw(self, cx, canvas);
     }
+
+    #[cfg(feature = "software")]
+    fn draw_software(&self, cx: &mut Context, canvas: &mut SoftwareCanvas) {
+        <T as View>::draw_software(self, cx, canvas);
+    }
 }
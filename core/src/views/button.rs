@@ -1,4 +1,4 @@
-use crate::{Context, Event, View};
+use crate::{activation_key_pressed, Context, Event, View};
 use crate::{Handle, MouseButton, WindowEvent};
 
 /// A simple push button with an action and a label.
@@ -15,9 +15,11 @@ impl Button {
         L: 'static + Fn(&mut Context) -> Handle<Label>,
         Label: 'static + View,
     {
-        Self { action: Some(Box::new(action)) }.build2(cx, move |cx| {
-            (label)(cx);
-        })
+        Self { action: Some(Box::new(action)) }
+            .build2(cx, move |cx| {
+                (label)(cx);
+            })
+            .focusable(true)
     }
 }
 
@@ -27,6 +29,14 @@ impl View for Button {
     }
 
     fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if activation_key_pressed(cx, event) {
+            if let Some(callback) = self.action.take() {
+                (callback)(cx);
+
+                self.action = Some(callback);
+            }
+        }
+
         if let Some(window_event) = event.message.downcast() {
             match window_event {
                 WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
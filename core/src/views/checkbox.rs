@@ -1,4 +1,4 @@
-use crate::{Context, Handle, MouseButton, Units::*, View, WindowEvent};
+use crate::{activation_key_pressed, Context, Handle, MouseButton, Units::*, View, WindowEvent};
 
 const ICON_CHECK: &str = "\u{2713}";
 
@@ -40,6 +40,7 @@ impl Checkbox {
     pub fn new(cx: &mut Context, checked: bool) -> Handle<Self> {
         Self { on_toggle: None }
             .build2(cx, |_| {})
+            .focusable(true)
             .width(Pixels(20.0))
             .height(Pixels(20.0))
             .text(if checked { ICON_CHECK } else { "" })
@@ -78,6 +79,14 @@ impl View for Checkbox {
     }
 
     fn event(&mut self, cx: &mut Context, event: &mut crate::Event) {
+        if activation_key_pressed(cx, event) {
+            if let Some(callback) = self.on_toggle.take() {
+                (callback)(cx);
+
+                self.on_toggle = Some(callback);
+            }
+        }
+
         if let Some(window_event) = event.message.downcast() {
             match window_event {
                 WindowEvent::MouseDown(button) if *button == MouseButton::Left => {
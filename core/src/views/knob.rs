@@ -80,6 +80,7 @@ impl Knob {
                 //     .rotate(30.0);
             });
         })
+        .focusable(true)
     }
     pub fn custom<'a, F, T>(
         cx: &'a mut Context,
@@ -115,6 +116,7 @@ impl Knob {
                 });
             });
         })
+        .focusable(true)
     }
 }
 
@@ -171,7 +173,7 @@ impl View for Knob {
                     self.prev_drag_y = cx.mouse.left.pos_down.1;
 
                     cx.captured = cx.current;
-                    cx.focused = cx.current;
+                    cx.focus(cx.current);
 
                     if let Some(slider_data) = cx.data::<SliderData>() {
                         self.continuous_normal = slider_data.value;
@@ -1,8 +1,16 @@
+use std::any::TypeId;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 use keyboard_types::Code;
 
-use crate::{Binding, Context, Data, Handle, Lens, Model, MouseButton, TreeExt, View, WindowEvent};
+use crate::{
+    Binding, Context, Data, Entity, Handle, Lens, Model, MouseButton, TreeError, TreeExt, View,
+    WindowEvent,
+};
 
 /// An `ItemPtr` is used to access an item from context in a list item template.
 ///
@@ -26,6 +34,8 @@ where
     index: usize,
     row: usize,
     col: usize,
+    // The list and the slot of the row for an item of a keyed list, used to look up its index
+    slot: Option<(Entity, usize)>,
 }
 
 // Manual implementations of Clone and Copy or else the compiler complains about a Clone bound on T which isn't actually required
@@ -33,7 +43,13 @@ impl<L, T> Copy for ItemPtr<L, T> where L: Lens<Target = Vec<T>> {}
 
 impl<L: Lens<Target = Vec<T>>, T> Clone for ItemPtr<L, T> {
     fn clone(&self) -> Self {
-        Self { lens: self.lens.clone(), index: self.index, row: self.row, col: self.col }
+        Self {
+            lens: self.lens.clone(),
+            index: self.index,
+            row: self.row,
+            col: self.col,
+            slot: self.slot,
+        }
     }
 }
 
@@ -43,14 +59,35 @@ where
 {
     /// Constructs a new ItemPtr from a lens and index.
     pub fn new(lens: L, index: usize, row: usize, col: usize) -> Self {
-        Self { lens, index, row, col }
+        Self { lens, index, row, col, slot: None }
+    }
+
+    // Constructs an ItemPtr for a row of a keyed list, which follows the item as the row is moved
+    fn keyed(lens: L, list: Entity, slot: usize, index: usize) -> Self {
+        Self { lens, index, row: index, col: 0, slot: Some((list, slot)) }
     }
 
     /// Returns the list index the ItemPtr refers to.
+    ///
+    /// For the rows of a keyed list this is the index of the item when the row was built. Use
+    /// [ItemPtr::current_index] to get the index of the item after the list has changed.
     pub fn index(&self) -> usize {
         self.index
     }
 
+    /// Returns the list index of the item at the time of the call.
+    ///
+    /// The rows of a keyed list are moved instead of rebuilt when items are inserted or removed
+    /// before them, so the index of their item can change after the row is built.
+    pub fn current_index(&self, cx: &Context) -> usize {
+        self.slot
+            .and_then(|(list, slot)| {
+                let model = cx.data.get(list)?.data.get(&TypeId::of::<RowIndices>())?;
+                model.downcast_ref::<RowIndices>()?.indices.get(&slot).copied()
+            })
+            .unwrap_or(self.index)
+    }
+
     pub fn row(&self) -> usize {
         self.row
     }
@@ -63,13 +100,31 @@ where
     where
         <L as Lens>::Source: 'static,
     {
+        let index = self.current_index(cx);
+
         self.lens
             .view(cx.data().expect("Failed to get data"))
-            .get(self.index)
-            .expect(&format!("Failed to get item: {}", self.index))
+            .get(index)
+            .expect(&format!("Failed to get item: {}", index))
     }
 }
 
+// The index of the item of each row of a keyed list, by the slot of the row
+#[derive(Default)]
+struct RowIndices {
+    indices: HashMap<usize, usize>,
+}
+
+impl Model for RowIndices {}
+
+// The rows of a keyed list from the last update
+struct KeyedRows<I> {
+    // The key, slot and entities of each row, in list order
+    rows: Vec<(I, usize, Vec<Entity>)>,
+    // The slot of the next row to be built
+    next_slot: usize,
+}
+
 pub trait DataHandle: Clone + Copy {
     type Data;
     fn get<'a>(&self, cx: &'a Context) -> &'a Self::Data;
@@ -136,7 +191,137 @@ impl<L: 'static + Lens<Target = Vec<T>>, T: Data> List<L, T> {
                 }
             });
         })
+        .focusable(true)
+    }
+
+    /// Creates a new ListView where each item is identified by a key.
+    ///
+    /// When the list changes, the rows of items with the same key as before are kept and moved to
+    /// the new position of their item, so any state in the row is kept. Only the rows of items
+    /// which have been inserted are built, and only the rows of items which have been removed are
+    /// destroyed.
+    ///
+    /// The template is not run again for a row which is kept, so views which show data of an item
+    /// that can change should bind to it. The `ItemPtr` of a row follows its item as it moves, see
+    /// [ItemPtr::current_index].
+    ///
+    /// # Example
+    /// ```ignore
+    /// List::new_keyed(cx, AppData::todos, |todo| todo.id, |cx, item| {
+    ///     Label::new(cx, &item.get(cx).name);
+    /// });
+    /// ```
+    pub fn new_keyed<K, I, F>(cx: &mut Context, lens: L, key: K, item: F) -> Handle<Self>
+    where
+        K: 'static + Fn(&T) -> I,
+        I: 'static + Hash + Eq,
+        F: 'static + Fn(&mut Context, ItemPtr<L, T>),
+        <L as Lens>::Source: Model,
+    {
+        let state = Rc::new(RefCell::new(KeyedRows::<I> { rows: Vec::new(), next_slot: 0 }));
+
+        List {
+            p: PhantomData::default(),
+            increment_callback: None,
+            decrement_callback: None,
+            clear_callback: None,
+        }
+        .build2(cx, move |cx| {
+            RowIndices::default().build(cx);
+
+            Binding::new(cx, lens.clone(), move |cx, list| {
+                let list_entity = cx.current;
+                let keys = list.get(cx).iter().map(|item| (key)(item)).collect::<Vec<_>>();
+                let mut state = state.borrow_mut();
+
+                let mut old_rows = HashMap::<I, VecDeque<(usize, Vec<Entity>)>>::new();
+                for (key, slot, entities) in state.rows.drain(..) {
+                    old_rows.entry(key).or_default().push_back((slot, entities));
+                }
+
+                // Match the items to the old rows, taking duplicate keys in order
+                let matched = keys
+                    .iter()
+                    .map(|key| old_rows.get_mut(key).and_then(|rows| rows.pop_front()))
+                    .collect::<Vec<_>>();
+
+                // Remove the rows which no longer have an item. The first child is the binding.
+                let kept = matched
+                    .iter()
+                    .flatten()
+                    .flat_map(|(_, entities)| entities.iter().copied())
+                    .collect::<HashSet<_>>();
+                let removed = list_entity
+                    .child_iter(&cx.tree)
+                    .skip(1)
+                    .filter(|child| !kept.contains(child))
+                    .collect::<Vec<_>>();
+                for child in removed {
+                    cx.remove(child);
+                }
+
+                // Give a slot to each inserted row
+                let mut next_slot = state.next_slot;
+                let rows = matched
+                    .into_iter()
+                    .map(|row| match row {
+                        Some((slot, entities)) => (slot, Some(entities)),
+                        None => {
+                            next_slot += 1;
+                            (next_slot - 1, None)
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                state.next_slot = next_slot;
+
+                // Update the index of each row before building the inserted rows
+                if let Some(indices) = cx
+                    .data
+                    .get_mut(list_entity)
+                    .and_then(|store| store.data.get_mut(&TypeId::of::<RowIndices>()))
+                    .and_then(|model| model.downcast::<RowIndices>())
+                {
+                    indices.indices =
+                        rows.iter().enumerate().map(|(index, (slot, _))| (*slot, index)).collect();
+                }
+
+                let mut prev = match cx.tree.get_first_child(list_entity) {
+                    Some(binding) => binding,
+                    None => return,
+                };
+
+                for (index, (key, (slot, entities))) in keys.into_iter().zip(rows).enumerate() {
+                    // Build an inserted row after the existing rows, kept rows are left as they are
+                    let entities = match entities {
+                        Some(entities) => entities,
+                        None => cx.with_current(list_entity, |cx| {
+                            let first = cx.count;
+                            (item)(cx, ItemPtr::keyed(lens.clone(), list_entity, slot, index));
+                            list_entity.child_iter(&cx.tree).skip(first).collect::<Vec<_>>()
+                        }),
+                    };
+
+                    // Move the row into place
+                    for entity in entities.iter() {
+                        move_after(cx, prev, *entity).expect("Failed to move list row");
+                        prev = *entity;
+                    }
+
+                    state.rows.push((key, slot, entities));
+                }
+            });
+        })
+        .focusable(true)
+    }
+}
+
+// Moves an entity to be the next sibling of another entity
+fn move_after(cx: &mut Context, prev: Entity, entity: Entity) -> Result<(), TreeError> {
+    if cx.tree.get_next_sibling(prev) == Some(entity) {
+        return Ok(());
     }
+
+    cx.insert_after(entity, prev)
 }
 
 impl<L: 'static + Lens<Target = Vec<T>>, T: Data> View for List<L, T> {
@@ -178,7 +363,7 @@ impl<L: 'static + Lens<Target = Vec<T>>, T: Data> View for List<L, T> {
 
         if let Some(WindowEvent::MouseDown(MouseButton::Left)) = event.message.downcast() {
             if !cx.focused.is_child_of(&cx.tree, cx.current) {
-                cx.focused = cx.current;
+                cx.focus(cx.current);
             }
         }
     }
@@ -0,0 +1,450 @@
+use std::rc::Rc;
+
+use morphorm::{LayoutType, PositionType};
+
+use crate::style::PropGet;
+use crate::{
+    close_all_menus, close_menu, close_menus_outside, focus_next, focus_prev, is_activation_key,
+    menu_of, open_menu, set_focus, show_menu, Code, Context, Display, Entity, Event, Handle, Label,
+    MouseButton, PropSet, TreeExt, View, WindowEvent,
+};
+
+const ICON_CHECK: &str = "\u{2713}";
+const ICON_RIGHT: &str = "\u{25B8}";
+
+/// A floating menu which contains menu items.
+///
+/// Menus are opened with `open_menu`, for example by `Handle::context_menu` or a `Submenu`. The
+/// Up and Down arrow keys move focus between the items of the menu, while Escape closes the menu.
+pub struct MenuPopup {}
+
+impl MenuPopup {
+    pub fn new<F>(cx: &mut Context, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        Self {}
+            .build2(cx, |cx| {
+                (content)(cx);
+            })
+            .position_type(PositionType::SelfDirected)
+            .focus_scope(true)
+            .z_order(100)
+    }
+}
+
+impl View for MenuPopup {
+    fn element(&self) -> Option<String> {
+        Some("menu".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                WindowEvent::KeyDown(Code::ArrowDown, _) => {
+                    focus_next(cx);
+                    event.consume();
+                }
+
+                WindowEvent::KeyDown(Code::ArrowUp, _) => {
+                    focus_prev(cx);
+                    event.consume();
+                }
+
+                WindowEvent::KeyDown(Code::Escape, _) => {
+                    close_menu(cx, cx.current);
+                    event.consume();
+                }
+
+                // Left closes a submenu, but not the first menu which was opened
+                WindowEvent::KeyDown(Code::ArrowLeft, _) => {
+                    if cx.menus.open_menus().first() != Some(&cx.current) {
+                        close_menu(cx, cx.current);
+                        event.consume();
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+/// An item of a menu which calls an action when it is clicked, or activated with Enter or Space.
+///
+/// Activating an item closes all open menus. Items can be disabled with `Handle::disabled`, and
+/// show a check mark when checked with `Handle::checked`.
+///
+/// # Example
+/// ```ignore
+/// Label::new(cx, "Clip").context_menu(|cx| {
+///     MenuItem::new(cx, "Duplicate", |cx| cx.emit(ClipEvent::Duplicate));
+///     MenuSeparator::new(cx);
+///     MenuItem::new(cx, "Delete", |cx| cx.emit(ClipEvent::Delete));
+/// });
+/// ```
+pub struct MenuItem {
+    action: Rc<dyn Fn(&mut Context)>,
+}
+
+impl MenuItem {
+    pub fn new<A>(cx: &mut Context, label: &str, action: A) -> Handle<Self>
+    where
+        A: 'static + Fn(&mut Context),
+    {
+        let label = label.to_owned();
+
+        Self { action: Rc::new(action) }
+            .build2(cx, move |cx| {
+                Label::new(cx, ICON_CHECK).class("check");
+                Label::new(cx, label.as_str());
+            })
+            .layout_type(LayoutType::Row)
+            .focusable(true)
+    }
+
+    fn activate(&mut self, cx: &mut Context) {
+        if cx.current.is_disabled(cx) {
+            return;
+        }
+
+        (self.action)(cx);
+
+        close_all_menus(cx);
+    }
+}
+
+impl<'a> Handle<'a, MenuItem> {
+    /// Binds a key sequence, such as `Ctrl+S`, to the action of the item and shows it in the item.
+    ///
+    /// The action is registered in the keymap under the given name, and is called with the focused
+    /// entity as the current entity when the keys are pressed, unless the item is disabled. The
+    /// action is removed along with the item. If the action already has a binding, such as one
+    /// loaded from a user's keymap, that binding is kept and shown instead.
+    pub fn shortcut(self, action: &str, keys: &str) -> Self {
+        let item = self.entity;
+
+        let item_action = self
+            .cx
+            .views
+            .get(&item)
+            .and_then(|view| view.downcast_ref::<MenuItem>())
+            .map(|menu_item| menu_item.action.clone());
+
+        if let Some(item_action) = item_action {
+            self.cx.keymap.add_entity_callback(item, action, move |cx| {
+                if !item.is_disabled(cx) {
+                    (item_action)(cx);
+                }
+            });
+        }
+
+        if self.cx.keymap.keys_for(action).is_none() {
+            if let Err(error) = self.cx.keymap.bind(None, keys, action) {
+                println!("Failed to parse shortcut {}: {}", keys, error);
+            }
+        }
+
+        let keys = self.cx.keymap.keys_for(action).map(|keys| keys.to_string()).unwrap_or_default();
+
+        // The label already exists if the item has been rebuilt
+        let existing =
+            item.child_iter(&self.cx.tree).find(|child| self.cx.menus.is_shortcut_label(*child));
+
+        let label = match existing {
+            Some(label) => label.set_text(self.cx, &keys),
+            None => self
+                .cx
+                .with_current(item, |cx| Label::new(cx, keys.as_str()).class("shortcut").entity),
+        };
+
+        self.cx.menus.set_shortcut_label(label, action);
+
+        self
+    }
+}
+
+impl View for MenuItem {
+    fn element(&self) -> Option<String> {
+        Some("menuitem".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                // Hovering an item closes the submenus of its siblings
+                WindowEvent::MouseEnter => {
+                    close_menus_outside(cx, cx.current);
+                }
+
+                WindowEvent::MouseUp(MouseButton::Left) => {
+                    self.activate(cx);
+                    event.consume();
+                }
+
+                WindowEvent::KeyDown(code, _)
+                    if is_activation_key(*code) && event.target == cx.current =>
+                {
+                    self.activate(cx);
+                    event.consume();
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+/// An item of a menu which opens a nested menu when it is hovered, clicked, or activated with
+/// Enter, Space or the Right arrow key.
+pub struct Submenu {
+    content: Option<Box<dyn Fn(&mut Context)>>,
+}
+
+impl Submenu {
+    pub fn new<F>(cx: &mut Context, label: &str, content: F) -> Handle<Self>
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let label = label.to_owned();
+
+        Self { content: Some(Box::new(content)) }
+            .build2(cx, move |cx| {
+                Label::new(cx, label.as_str());
+                Label::new(cx, ICON_RIGHT).class("arrow");
+            })
+            .layout_type(LayoutType::Row)
+            .focusable(true)
+    }
+
+    fn open(&mut self, cx: &mut Context) {
+        if cx.current.is_disabled(cx) || menu_of(cx, cx.current).is_some() {
+            return;
+        }
+
+        if let Some(content) = self.content.take() {
+            let left = cx.cache.get_width(cx.current);
+            open_menu(cx, cx.current, left, 0.0, &*content);
+
+            self.content = Some(content);
+        }
+    }
+}
+
+impl View for Submenu {
+    fn element(&self) -> Option<String> {
+        Some("submenu".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        // Events from the items of the nested menu bubble up through the submenu
+        let from_menu = menu_of(cx, cx.current)
+            .map_or(false, |menu| event.target.parent_iter(&cx.tree).any(|e| e == menu));
+
+        if from_menu {
+            return;
+        }
+
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                WindowEvent::MouseEnter => {
+                    close_menus_outside(cx, cx.current);
+                    self.open(cx);
+                }
+
+                WindowEvent::MouseUp(MouseButton::Left) => {
+                    self.open(cx);
+                    event.consume();
+                }
+
+                WindowEvent::KeyDown(code, _)
+                    if is_activation_key(*code) || *code == Code::ArrowRight =>
+                {
+                    self.open(cx);
+                    event.consume();
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+/// A horizontal bar of menus, such as File, Edit and View.
+///
+/// While a menu of the bar is open, hovering the title of another menu opens that menu instead,
+/// and the Left and Right arrow keys move between the menus.
+///
+/// # Example
+/// ```ignore
+/// MenuBar::new(cx, |cx| {
+///     Menu::new(cx, "File", |cx| {
+///         MenuItem::new(cx, "Save", |cx| cx.emit(AppEvent::Save)).shortcut("save", "Ctrl+S");
+///     });
+/// });
+/// ```
+pub struct MenuBar {}
+
+impl MenuBar {
+    pub fn new<F>(cx: &mut Context, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        Self {}
+            .build2(cx, |cx| {
+                (content)(cx);
+            })
+            .layout_type(LayoutType::Row)
+    }
+}
+
+impl View for MenuBar {
+    fn element(&self) -> Option<String> {
+        Some("menubar".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if let Some(window_event) = event.message.downcast() {
+            let step = match window_event {
+                WindowEvent::KeyDown(Code::ArrowRight, _) => 1,
+                WindowEvent::KeyDown(Code::ArrowLeft, _) => -1,
+                _ => return,
+            };
+
+            let menus = cx
+                .current
+                .child_iter(&cx.tree)
+                .filter(|child| menu_popup(cx, *child).is_some())
+                .collect::<Vec<_>>();
+
+            // The menu which is open, or whose title or items contain the focus
+            let active = menus.iter().position(|menu| {
+                menu_of(cx, *menu).is_some() || cx.focused.parent_iter(&cx.tree).any(|e| e == *menu)
+            });
+
+            if let Some(index) = active {
+                let next = menus[(index as isize + step).rem_euclid(menus.len() as isize) as usize];
+
+                if menu_of(cx, menus[index]).is_some() {
+                    open_bar_menu(cx, next);
+                } else {
+                    set_focus(cx, next);
+                }
+
+                event.consume();
+            }
+        }
+    }
+}
+
+/// A menu of a menu bar, with a title which opens the menu when it is clicked, or activated with
+/// Enter, Space or the Down arrow key.
+///
+/// The content of the menu is built once and hidden while the menu is closed, so that the
+/// shortcuts of its items are available before the menu is first opened.
+pub struct Menu {}
+
+impl Menu {
+    pub fn new<F>(cx: &mut Context, label: &str, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        let label = label.to_owned();
+
+        Self {}
+            .build2(cx, move |cx| {
+                Label::new(cx, label.as_str());
+
+                let popup = MenuPopup::new(cx, content).display(Display::None).entity;
+                cx.menus.set_persistent(popup);
+            })
+            .focusable(true)
+    }
+}
+
+impl View for Menu {
+    fn element(&self) -> Option<String> {
+        Some("menutitle".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        let popup = match menu_popup(cx, cx.current) {
+            Some(popup) => popup,
+            None => return,
+        };
+
+        // Events from the items of the menu bubble up through the title
+        if event.target.parent_iter(&cx.tree).any(|e| e == popup) {
+            return;
+        }
+
+        let is_open = menu_of(cx, cx.current).is_some();
+
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                WindowEvent::MouseDown(MouseButton::Left) => {
+                    if is_open {
+                        close_menu(cx, popup);
+                    } else {
+                        open_bar_menu(cx, cx.current);
+                    }
+
+                    event.consume();
+                }
+
+                // Switch to this menu if another menu of the same bar is open
+                WindowEvent::MouseEnter => {
+                    let bar = cx.tree.get_parent(cx.current);
+                    let other_open = cx.menus.open_menus().first().map_or(false, |open| {
+                        let title = cx.tree.get_parent(*open);
+                        title != Some(cx.current)
+                            && title.and_then(|title| cx.tree.get_parent(title)) == bar
+                    });
+
+                    if other_open {
+                        open_bar_menu(cx, cx.current);
+                    }
+                }
+
+                WindowEvent::KeyDown(code, _)
+                    if (is_activation_key(*code) || *code == Code::ArrowDown)
+                        && event.target == cx.current
+                        && !is_open =>
+                {
+                    open_bar_menu(cx, cx.current);
+                    event.consume();
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
+
+// Returns the menu of a menu bar title
+fn menu_popup(cx: &Context, title: Entity) -> Option<Entity> {
+    title.child_iter(&cx.tree).find(|child| cx.menus.is_persistent(*child))
+}
+
+// Shows the menu of a menu bar title below the title
+fn open_bar_menu(cx: &mut Context, title: Entity) {
+    if let Some(popup) = menu_popup(cx, title) {
+        let top = cx.cache.get_height(title);
+        show_menu(cx, popup, 0.0, top);
+    }
+}
+
+/// A line which separates groups of items in a menu.
+pub struct MenuSeparator {}
+
+impl MenuSeparator {
+    pub fn new(cx: &mut Context) -> Handle<Self> {
+        Self {}.build(cx)
+    }
+}
+
+impl View for MenuSeparator {
+    fn element(&self) -> Option<String> {
+        Some("menuseparator".to_string())
+    }
+}
@@ -42,3 +42,18 @@ pub use popup::*;
 
 mod radio_buttons;
 pub use radio_buttons::*;
+
+mod tooltip;
+pub use tooltip::Tooltip;
+
+mod menu;
+pub use menu::*;
+
+mod portal;
+pub use portal::Portal;
+
+mod scroll_view;
+pub use scroll_view::*;
+
+mod virtual_list;
+pub use virtual_list::VirtualList;
@@ -3,7 +3,7 @@ use std::marker::PhantomData;
 use crate::Units::*;
 use crate::{
     Actions, Binding, Button, Color, Context, Data, Field, Handle, Label, Lens, Model, Overflow,
-    Popup, PopupData, PopupEvent, View,
+    Placement, Popup, PopupData, PopupEvent, TreeExt, View,
 };
 
 pub struct Picker<L>
@@ -90,18 +90,37 @@ impl Dropdown {
 
                 (label)(cx).class("title").on_press(|cx| cx.emit(PopupEvent::Switch));
 
-                Popup::new(cx, move |cx| {
+                // The list is placed in window coordinates so that it is not clipped by the
+                // parent views
+                let popup = Popup::new(cx, move |cx| {
                     (builder)(cx);
                 })
-                .top(Percentage(100.0))
-                .width(Stretch(1.0))
+                .placement(Placement::Below)
                 .height(Auto)
-                .overflow(Overflow::Visible);
+                .overflow(Overflow::Visible)
+                .entity;
+
+                cx.portals.set_match_width(popup, true);
             })
             .size(Auto)
     }
 }
 
+impl<'a> Handle<'a, Dropdown> {
+    /// Sets where the list of the dropdown is placed relative to the dropdown.
+    pub fn placement(self, placement: Placement) -> Self {
+        let popup =
+            self.entity.child_iter(&self.cx.tree).find(|child| self.cx.portals.is_portal(*child));
+
+        if let Some(popup) = popup {
+            self.cx.portals.set_placement(popup, placement);
+            self.cx.style.needs_relayout = true;
+        }
+
+        self
+    }
+}
+
 impl View for Dropdown {
     fn element(&self) -> Option<String> {
         Some("dropdown".to_string())
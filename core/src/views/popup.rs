@@ -1,8 +1,8 @@
 use morphorm::PositionType;
 
 use crate::{
-    style::PropGet, Binding, Code, Context, Data, Handle, Lens, Model, PropSet, View, Visibility,
-    WindowEvent,
+    style::PropGet, Binding, Code, Context, Data, Handle, Lens, Model, Placement, PropSet, View,
+    Visibility, WindowEvent,
 };
 
 #[derive(Debug, Default, Data, Lens, Clone)]
@@ -94,6 +94,26 @@ impl Popup {
     }
 }
 
+impl<'a> Handle<'a, Popup> {
+    /// Places the popup next to its parent in window coordinates, instead of with normal layout.
+    ///
+    /// The popup is flipped to the opposite side of its parent, or shifted, to keep it inside the
+    /// window, and is not clipped by the views which contain it. It follows its parent when the
+    /// geometry of the parent changes or the window is resized.
+    pub fn placement(self, placement: Placement) -> Self {
+        if let Some(parent) = self.cx.tree.get_parent(self.entity) {
+            if self.cx.portals.anchor(self.entity).is_none() {
+                self.cx.portals.set_anchor(self.entity, parent);
+            }
+
+            self.cx.portals.set_placement(self.entity, placement);
+            self.cx.style.needs_relayout = true;
+        }
+
+        self
+    }
+}
+
 impl View for Popup {
     fn element(&self) -> Option<String> {
         Some("popup".to_string())
@@ -0,0 +1,64 @@
+use morphorm::PositionType;
+
+use crate::{Context, Entity, Handle, Placement, Units::Auto, View};
+
+/// An overlay which shows its content next to an anchor entity, above all other views.
+///
+/// The content is laid out in window coordinates below the anchor by default, or on the side of the
+/// anchor set with `placement`, and is flipped or shifted to stay inside the window. It is not clipped by the views
+/// which contain the portal, such as a scrolled panel, but it is still part of the subtree in which
+/// it is built, so it can use the data of that subtree and its events propagate up through it.
+///
+/// # Example
+/// ```ignore
+/// let button = Button::new(cx, |cx| cx.emit(PopupEvent::Switch), |cx| Label::new(cx, "Open"))
+///     .entity;
+/// Portal::new(cx, button, |cx| {
+///     Label::new(cx, "Shown below the button");
+/// });
+/// ```
+pub struct Portal {}
+
+impl Portal {
+    pub fn new<F>(cx: &mut Context, anchor: Entity, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        let handle = Self {}
+            .build2(cx, |cx| {
+                (content)(cx);
+            })
+            .position_type(PositionType::SelfDirected)
+            .size(Auto)
+            .z_order(100);
+
+        handle.cx.portals.set_anchor(handle.entity, anchor);
+
+        handle
+    }
+}
+
+impl<'a> Handle<'a, Portal> {
+    /// Sets where the portal is placed relative to its anchor.
+    pub fn placement(self, placement: Placement) -> Self {
+        self.cx.portals.set_placement(self.entity, placement);
+        self.cx.style.needs_relayout = true;
+
+        self
+    }
+
+    /// Sets whether the width of the portal is set to the width of its anchor, such as for the
+    /// list of a dropdown.
+    pub fn match_anchor_width(self, flag: bool) -> Self {
+        self.cx.portals.set_match_width(self.entity, flag);
+        self.cx.style.needs_relayout = true;
+
+        self
+    }
+}
+
+impl View for Portal {
+    fn element(&self) -> Option<String> {
+        Some("portal".to_string())
+    }
+}
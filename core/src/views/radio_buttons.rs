@@ -1,5 +1,7 @@
 use crate::style::PropGet;
-use crate::{Canvas, Context, Event, Handle, MouseButton, Units, View, WindowEvent};
+use crate::{
+    activation_key_pressed, Canvas, Context, Event, Handle, MouseButton, Units, View, WindowEvent,
+};
 use femtovg::{Paint, Path};
 
 pub struct RadioButton {
@@ -8,7 +10,7 @@ pub struct RadioButton {
 
 impl RadioButton {
     pub fn new(cx: &mut Context, checked: bool) -> Handle<Self> {
-        Self { on_select: None }.build2(cx, |_| {}).checked(checked)
+        Self { on_select: None }.build2(cx, |_| {}).focusable(true).checked(checked)
     }
 }
 
@@ -18,12 +20,25 @@ impl View for RadioButton {
     }
 
     fn event(&mut self, cx: &mut Context, event: &mut Event) {
-        if let Some(WindowEvent::MouseDown(MouseButton::Left)) = event.message.downcast() {
+        if activation_key_pressed(cx, event) {
             if let Some(callback) = self.on_select.take() {
                 (callback)(cx);
                 self.on_select = Some(callback);
             }
         }
+
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                WindowEvent::MouseDown(MouseButton::Left) => {
+                    if let Some(callback) = self.on_select.take() {
+                        (callback)(cx);
+                        self.on_select = Some(callback);
+                    }
+                }
+
+                _ => {}
+            }
+        }
     }
 
     fn draw(&self, cx: &mut Context, canvas: &mut Canvas) {
@@ -0,0 +1,559 @@
+use std::time::Duration;
+
+use morphorm::PositionType;
+
+use crate::{
+    Context, Display, Element, Entity, Event, Handle, Lens, Model, Modifiers, MouseButton,
+    Orientation, PropSet, Propagation, Res, TimerId, TreeExt, Units::*, VStack, View, WindowEvent,
+};
+
+// The distance scrolled for each line of a mouse wheel
+static SCROLL_SENSITIVITY: f32 = 35.0;
+// The fraction of the remaining distance which is scrolled in each step of smooth scrolling
+static SMOOTH_FACTOR: f32 = 0.3;
+// The interval between the steps of smooth scrolling
+static SMOOTH_INTERVAL: Duration = Duration::from_millis(16);
+// The minimum length of a scrollbar thumb
+static MIN_THUMB_LENGTH: f32 = 20.0;
+
+/// The scroll position of a [ScrollView], and the sizes of its viewport and content.
+///
+/// The data is built by the scroll view, so the views inside it can bind to the scroll position
+/// through lenses such as `ScrollData::scroll_y`.
+#[derive(Debug, Default, Clone, Lens)]
+pub struct ScrollData {
+    /// The horizontal distance the content is scrolled by, in pixels.
+    pub scroll_x: f32,
+    /// The vertical distance the content is scrolled by, in pixels.
+    pub scroll_y: f32,
+    pub content_width: f32,
+    pub content_height: f32,
+    pub viewport_width: f32,
+    pub viewport_height: f32,
+}
+
+impl ScrollData {
+    // Returns the maximum scroll distances
+    fn max_scroll(&self) -> (f32, f32) {
+        (
+            (self.content_width - self.viewport_width).max(0.0),
+            (self.content_height - self.viewport_height).max(0.0),
+        )
+    }
+}
+
+impl Model for ScrollData {
+    fn event(&mut self, _: &mut Context, event: &mut Event) {
+        if let Some(ScrollEventInternal::Update(data)) = event.message.downcast() {
+            *self = data.clone();
+        }
+    }
+}
+
+/// Events which scroll the nearest [ScrollView] which contains the sender.
+#[derive(Debug, Clone, Copy)]
+pub enum ScrollEvent {
+    /// Sets the horizontal scroll distance, in pixels.
+    SetX(f32),
+    /// Sets the vertical scroll distance, in pixels.
+    SetY(f32),
+    /// Scrolls by the given horizontal and vertical distances, in pixels.
+    ScrollBy(f32, f32),
+}
+
+#[derive(Debug, Clone)]
+enum ScrollEventInternal {
+    // Updates the scroll data which can be bound to
+    Update(ScrollData),
+    // Moves towards the target of smooth scrolling
+    Step,
+}
+
+/// A container which clips its content and scrolls it with the mouse wheel or its scrollbars.
+///
+/// The content is laid out at its own size, which is the width of the scroll view and the height
+/// of the content for a vertical scroll view, and is moved by the scroll distance. The scroll
+/// position is stored in [ScrollData], and can be set by sending a [ScrollEvent], or from the
+/// app's model with [Handle::scroll_y] inside a binding.
+///
+/// # Example
+/// ```ignore
+/// Binding::new(cx, AppData::scroll, |cx, scroll| {
+///     ScrollView::new(cx, false, true, |cx| {
+///         for i in 0..100 {
+///             Label::new(cx, &i.to_string());
+///         }
+///     })
+///     .smooth(true)
+///     .scroll_y(scroll)
+///     .on_scroll(|cx, _, y| cx.emit(AppEvent::SetScroll(y)));
+/// });
+/// ```
+pub struct ScrollView {
+    data: ScrollData,
+    smooth: bool,
+    // The scroll distances which smooth scrolling is moving towards
+    target: Option<(f32, f32)>,
+    timer: Option<TimerId>,
+    // Set once the scroll view has been laid out, so that scroll distances can be clamped
+    laid_out: bool,
+    // Scroll distances which were set before the scroll view was laid out
+    pending: (Option<f32>, Option<f32>),
+    on_scroll: Option<Box<dyn Fn(&mut Context, f32, f32)>>,
+}
+
+impl ScrollView {
+    pub fn new<F>(cx: &mut Context, scroll_x: bool, scroll_y: bool, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        Self {
+            data: ScrollData::default(),
+            smooth: false,
+            target: None,
+            timer: None,
+            laid_out: false,
+            pending: (None, None),
+            on_scroll: None,
+        }
+        .build2(cx, move |cx| {
+            ScrollData::default().build(cx);
+
+            VStack::new(cx, content)
+                .class("scroll_content")
+                .position_type(PositionType::SelfDirected)
+                .width(if scroll_x { Auto } else { Stretch(1.0) })
+                .height(if scroll_y { Auto } else { Stretch(1.0) });
+
+            Scrollbar::new(cx, Orientation::Horizontal);
+            Scrollbar::new(cx, Orientation::Vertical);
+        })
+    }
+
+    // Sets the scroll distances, clamped to the size of the content, and moves the content
+    fn set_scroll(&mut self, cx: &mut Context, x: f32, y: f32) {
+        let content = match content(cx, cx.current) {
+            Some(content) => content,
+            None => return,
+        };
+
+        let mut data = ScrollData {
+            content_width: cx.cache.get_width(content),
+            content_height: cx.cache.get_height(content),
+            viewport_width: cx.cache.get_width(cx.current),
+            viewport_height: cx.cache.get_height(cx.current),
+            ..self.data.clone()
+        };
+
+        let (max_x, max_y) = data.max_scroll();
+        data.scroll_x = x.clamp(0.0, max_x);
+        data.scroll_y = y.clamp(0.0, max_y);
+
+        let scrolled = data.scroll_x != self.data.scroll_x || data.scroll_y != self.data.scroll_y;
+        let resized = data.content_width != self.data.content_width
+            || data.content_height != self.data.content_height
+            || data.viewport_width != self.data.viewport_width
+            || data.viewport_height != self.data.viewport_height;
+
+        if !scrolled && !resized {
+            return;
+        }
+
+        self.data = data.clone();
+
+        content.set_left(cx, Pixels(-data.scroll_x)).set_top(cx, Pixels(-data.scroll_y));
+
+        for scrollbar in scrollbars(cx, cx.current) {
+            let fits = match orientation(cx, scrollbar) {
+                Some(Orientation::Horizontal) => data.content_width <= data.viewport_width,
+                Some(Orientation::Vertical) => data.content_height <= data.viewport_height,
+                None => continue,
+            };
+
+            scrollbar.set_display(cx, if fits { Display::None } else { Display::Flex });
+            update_thumb(cx, scrollbar, &data);
+        }
+
+        cx.event_queue.push_back(
+            Event::new(ScrollEventInternal::Update(data.clone()))
+                .target(cx.current)
+                .origin(cx.current)
+                .propagate(Propagation::Direct),
+        );
+
+        if scrolled {
+            if let Some(callback) = &self.on_scroll {
+                (callback)(cx, data.scroll_x, data.scroll_y);
+            }
+        }
+    }
+
+    // Sets the given scroll distances, or keeps them until the scroll view has been laid out
+    fn request_scroll(&mut self, cx: &mut Context, x: Option<f32>, y: Option<f32>) {
+        if self.laid_out {
+            self.set_scroll(cx, x.unwrap_or(self.data.scroll_x), y.unwrap_or(self.data.scroll_y));
+        } else {
+            self.pending = (x.or(self.pending.0), y.or(self.pending.1));
+        }
+    }
+
+    // Scrolls by the given distances, smoothly if smooth scrolling is enabled
+    fn scroll_by(&mut self, cx: &mut Context, dx: f32, dy: f32) {
+        if !self.smooth {
+            self.set_scroll(cx, self.data.scroll_x + dx, self.data.scroll_y + dy);
+            return;
+        }
+
+        let (max_x, max_y) = self.data.max_scroll();
+        let (x, y) = self.target.unwrap_or((self.data.scroll_x, self.data.scroll_y));
+        self.target = Some(((x + dx).clamp(0.0, max_x), (y + dy).clamp(0.0, max_y)));
+
+        if self.timer.is_none() {
+            self.timer = Some(cx.add_timer(SMOOTH_INTERVAL, true, ScrollEventInternal::Step));
+        }
+    }
+
+    // Moves a step towards the target of smooth scrolling
+    fn step(&mut self, cx: &mut Context) {
+        let (target_x, target_y) = self.target.unwrap_or((self.data.scroll_x, self.data.scroll_y));
+
+        let dx = target_x - self.data.scroll_x;
+        let dy = target_y - self.data.scroll_y;
+
+        if dx.abs() < 0.5 && dy.abs() < 0.5 {
+            self.set_scroll(cx, target_x, target_y);
+            self.target = None;
+
+            if let Some(timer) = self.timer.take() {
+                cx.cancel_timer(timer);
+            }
+        } else {
+            self.set_scroll(
+                cx,
+                self.data.scroll_x + dx * SMOOTH_FACTOR,
+                self.data.scroll_y + dy * SMOOTH_FACTOR,
+            );
+        }
+    }
+}
+
+impl<'a> Handle<'a, ScrollView> {
+    /// Sets whether scrolling with the mouse wheel animates to the new position.
+    pub fn smooth(self, flag: bool) -> Self {
+        if let Some(view) = self.cx.views.get_mut(&self.entity) {
+            if let Some(scroll_view) = view.downcast_mut::<ScrollView>() {
+                scroll_view.smooth = flag;
+            }
+        }
+
+        self
+    }
+
+    /// Sets the horizontal scroll distance, in pixels.
+    ///
+    /// Inside a binding, this lets the app's model drive the scroll position. The distance is
+    /// clamped to the size of the content once the scroll view has been laid out.
+    pub fn scroll_x(self, distance: impl Res<f32>) -> Self {
+        let distance = *distance.get(self.cx);
+        self.emit_scroll(ScrollEvent::SetX(distance))
+    }
+
+    /// Sets the vertical scroll distance, in pixels.
+    ///
+    /// Inside a binding, this lets the app's model drive the scroll position. The distance is
+    /// clamped to the size of the content once the scroll view has been laid out.
+    pub fn scroll_y(self, distance: impl Res<f32>) -> Self {
+        let distance = *distance.get(self.cx);
+        self.emit_scroll(ScrollEvent::SetY(distance))
+    }
+
+    // Sends a scroll event to the scroll view
+    fn emit_scroll(self, scroll_event: ScrollEvent) -> Self {
+        self.cx.event_queue.push_back(
+            Event::new(scroll_event)
+                .target(self.entity)
+                .origin(self.entity)
+                .propagate(Propagation::Direct),
+        );
+
+        self
+    }
+
+    /// Sets the callback triggered when the scroll position has changed.
+    ///
+    /// Takes a closure which provides the horizontal and vertical scroll distances, in pixels.
+    pub fn on_scroll<F>(self, callback: F) -> Self
+    where
+        F: 'static + Fn(&mut Context, f32, f32),
+    {
+        if let Some(view) = self.cx.views.get_mut(&self.entity) {
+            if let Some(scroll_view) = view.downcast_mut::<ScrollView>() {
+                scroll_view.on_scroll = Some(Box::new(callback));
+            }
+        }
+
+        self
+    }
+}
+
+impl View for ScrollView {
+    fn element(&self) -> Option<String> {
+        Some("scrollview".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                // The size of the viewport or of the content has changed
+                WindowEvent::GeometryChanged(_) => {
+                    self.laid_out = true;
+
+                    let (x, y) = std::mem::take(&mut self.pending);
+                    self.set_scroll(
+                        cx,
+                        x.unwrap_or(self.data.scroll_x),
+                        y.unwrap_or(self.data.scroll_y),
+                    );
+                }
+
+                WindowEvent::MouseScroll(x, y) => {
+                    // Scroll horizontally with Shift and a vertical mouse wheel
+                    let (x, y) = if cx.modifiers.contains(Modifiers::SHIFT) && *x == 0.0 {
+                        (*y, 0.0)
+                    } else {
+                        (*x, *y)
+                    };
+
+                    let (max_x, max_y) = self.data.max_scroll();
+                    if (x != 0.0 && max_x > 0.0) || (y != 0.0 && max_y > 0.0) {
+                        self.scroll_by(cx, -x * SCROLL_SENSITIVITY, -y * SCROLL_SENSITIVITY);
+                        event.consume();
+                    }
+                }
+
+                _ => {}
+            }
+        }
+
+        if let Some(scroll_event) = event.message.downcast() {
+            match scroll_event {
+                ScrollEvent::SetX(x) => self.request_scroll(cx, Some(*x), None),
+                ScrollEvent::SetY(y) => self.request_scroll(cx, None, Some(*y)),
+                ScrollEvent::ScrollBy(dx, dy) => {
+                    self.set_scroll(cx, self.data.scroll_x + dx, self.data.scroll_y + dy)
+                }
+            }
+
+            self.target = None;
+            event.consume();
+        }
+
+        if let Some(ScrollEventInternal::Step) = event.message.downcast() {
+            self.step(cx);
+        }
+    }
+}
+
+/// Scrolls the scroll views which contain an entity, such as a focused child, to bring it into view.
+pub fn scroll_to(cx: &mut Context, entity: Entity) {
+    let scroll_views = entity
+        .parent_iter(&cx.tree)
+        .skip(1)
+        .filter(|ancestor| {
+            cx.views.get(ancestor).map_or(false, |view| view.downcast_ref::<ScrollView>().is_some())
+        })
+        .collect::<Vec<_>>();
+
+    let bounds = cx.cache.get_bounds(entity);
+
+    for scroll_view in scroll_views {
+        let viewport = cx.cache.get_bounds(scroll_view);
+
+        // Align with the start of the viewport if the entity is larger than it
+        let distance = |start: f32, length: f32, view_start: f32, view_length: f32| {
+            if start < view_start || length > view_length {
+                start - view_start
+            } else if start + length > view_start + view_length {
+                start + length - (view_start + view_length)
+            } else {
+                0.0
+            }
+        };
+
+        let dx = distance(bounds.x, bounds.w, viewport.x, viewport.w);
+        let dy = distance(bounds.y, bounds.h, viewport.y, viewport.h);
+
+        if dx != 0.0 || dy != 0.0 {
+            cx.event_queue.push_back(
+                Event::new(ScrollEvent::ScrollBy(dx, dy))
+                    .target(scroll_view)
+                    .origin(entity)
+                    .propagate(Propagation::Direct),
+            );
+        }
+    }
+}
+
+// Returns the content of a scroll view
+fn content(cx: &Context, scroll_view: Entity) -> Option<Entity> {
+    scroll_view.child_iter(&cx.tree).next()
+}
+
+// Returns the scrollbars of a scroll view
+fn scrollbars(cx: &Context, scroll_view: Entity) -> Vec<Entity> {
+    scroll_view.child_iter(&cx.tree).filter(|child| orientation(cx, *child).is_some()).collect()
+}
+
+// Returns the orientation of a scrollbar, or `None` if the entity is not a scrollbar
+fn orientation(cx: &Context, entity: Entity) -> Option<Orientation> {
+    cx.views
+        .get(&entity)
+        .and_then(|view| view.downcast_ref::<Scrollbar>())
+        .map(|scrollbar| scrollbar.orientation)
+}
+
+// Returns the length and position of the thumb of a scrollbar
+fn thumb_geometry(orientation: Orientation, data: &ScrollData) -> (f32, f32) {
+    let (scroll, content, viewport) = match orientation {
+        Orientation::Horizontal => (data.scroll_x, data.content_width, data.viewport_width),
+        Orientation::Vertical => (data.scroll_y, data.content_height, data.viewport_height),
+    };
+
+    if content <= viewport {
+        return (viewport, 0.0);
+    }
+
+    let length = (viewport * viewport / content).max(MIN_THUMB_LENGTH).min(viewport);
+    let position = (viewport - length) * scroll / (content - viewport);
+
+    (length, position)
+}
+
+// Moves the thumb of a scrollbar to show the scroll position
+fn update_thumb(cx: &mut Context, scrollbar: Entity, data: &ScrollData) {
+    let orientation = match orientation(cx, scrollbar) {
+        Some(orientation) => orientation,
+        None => return,
+    };
+
+    let thumb = match scrollbar.child_iter(&cx.tree).next() {
+        Some(thumb) => thumb,
+        None => return,
+    };
+
+    let (length, position) = thumb_geometry(orientation, data);
+
+    match orientation {
+        Orientation::Horizontal => {
+            thumb.set_left(cx, Pixels(position)).set_width(cx, Pixels(length));
+        }
+
+        Orientation::Vertical => {
+            thumb.set_top(cx, Pixels(position)).set_height(cx, Pixels(length));
+        }
+    }
+}
+
+// A scrollbar of a scroll view, with a thumb which can be dragged
+struct Scrollbar {
+    orientation: Orientation,
+    // The cursor position and scroll distance when the thumb started being dragged
+    drag_start: Option<(f32, f32)>,
+}
+
+impl Scrollbar {
+    fn new(cx: &mut Context, orientation: Orientation) -> Handle<Self> {
+        let handle = Self { orientation, drag_start: None }
+            .build2(cx, |cx| {
+                Element::new(cx).class("thumb").position_type(PositionType::SelfDirected);
+            })
+            .position_type(PositionType::SelfDirected)
+            .display(Display::None);
+
+        match orientation {
+            Orientation::Horizontal => {
+                handle.class("horizontal").top(Stretch(1.0)).bottom(Pixels(0.0))
+            }
+            Orientation::Vertical => handle.class("vertical").left(Stretch(1.0)).right(Pixels(0.0)),
+        }
+    }
+
+    // Returns the position of the cursor along the scrollbar
+    fn cursor(&self, cx: &Context) -> f32 {
+        match self.orientation {
+            Orientation::Horizontal => cx.mouse.cursorx - cx.cache.get_posx(cx.current),
+            Orientation::Vertical => cx.mouse.cursory - cx.cache.get_posy(cx.current),
+        }
+    }
+
+    // Sends the scroll distance along the scrollbar
+    fn scroll(&self, cx: &mut Context, distance: f32) {
+        match self.orientation {
+            Orientation::Horizontal => cx.emit(ScrollEvent::SetX(distance)),
+            Orientation::Vertical => cx.emit(ScrollEvent::SetY(distance)),
+        }
+    }
+}
+
+impl View for Scrollbar {
+    fn element(&self) -> Option<String> {
+        Some("scrollbar".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        let data = match cx.data::<ScrollData>() {
+            Some(data) => data.clone(),
+            None => return,
+        };
+
+        let (length, position) = thumb_geometry(self.orientation, &data);
+        let (scroll, max_scroll) = match self.orientation {
+            Orientation::Horizontal => (data.scroll_x, data.max_scroll().0),
+            Orientation::Vertical => (data.scroll_y, data.max_scroll().1),
+        };
+
+        // The scroll distance for each pixel that the thumb moves
+        let track = match self.orientation {
+            Orientation::Horizontal => data.viewport_width,
+            Orientation::Vertical => data.viewport_height,
+        };
+        let ratio = if track > length { max_scroll / (track - length) } else { 0.0 };
+
+        if let Some(window_event) = event.message.downcast() {
+            match window_event {
+                WindowEvent::MouseDown(MouseButton::Left) => {
+                    let cursor = self.cursor(cx);
+
+                    // Pressing the track outside of the thumb centers the thumb on the cursor
+                    let scroll = if cursor < position || cursor > position + length {
+                        let scroll = (cursor - length / 2.0) * ratio;
+                        self.scroll(cx, scroll);
+                        scroll.clamp(0.0, max_scroll)
+                    } else {
+                        scroll
+                    };
+
+                    self.drag_start = Some((cursor, scroll));
+                    cx.captured = cx.current;
+                    event.consume();
+                }
+
+                WindowEvent::MouseMove(_, _) => {
+                    if let Some((start_cursor, start_scroll)) = self.drag_start {
+                        let distance = start_scroll + (self.cursor(cx) - start_cursor) * ratio;
+                        self.scroll(cx, distance);
+                    }
+                }
+
+                WindowEvent::MouseUp(MouseButton::Left) => {
+                    if self.drag_start.take().is_some() {
+                        cx.captured = Entity::null();
+                        event.consume();
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+}
@@ -195,6 +195,7 @@ impl Slider {
                     });
                 });
             })
+            .focusable(true)
     }
 }
 
@@ -311,6 +311,7 @@ where
                 });
             });
         })
+        .focusable(true)
         //.text(text.as_str())
     }
 
@@ -825,7 +826,7 @@ where
                         if !self.edit {
                             self.edit = true;
                             cx.emit(TextEvent::SetEditing(true));
-                            cx.focused = cx.current;
+                            cx.focus(cx.current);
                             cx.captured = cx.current;
                             cx.current.set_checked(cx, true);
                         }
@@ -0,0 +1,29 @@
+use morphorm::PositionType;
+
+use crate::{Context, Handle, View};
+
+/// The overlay which shows the tooltip of a view.
+///
+/// Tooltips are created and removed by the tooltip system, and are styled with the `tooltip`
+/// element selector. See `Handle::tooltip` and `Handle::tooltip_content`.
+pub struct Tooltip {}
+
+impl Tooltip {
+    pub fn new<F>(cx: &mut Context, content: F) -> Handle<Self>
+    where
+        F: 'static + FnOnce(&mut Context),
+    {
+        Self {}
+            .build2(cx, |cx| {
+                (content)(cx);
+            })
+            .position_type(PositionType::SelfDirected)
+            .z_order(i32::MAX)
+    }
+}
+
+impl View for Tooltip {
+    fn element(&self) -> Option<String> {
+        Some("tooltip".to_string())
+    }
+}
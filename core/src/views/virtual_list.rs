@@ -0,0 +1,201 @@
+use std::rc::Rc;
+
+use morphorm::PositionType;
+
+use crate::{
+    Binding, Context, Data, Display, Entity, Event, Handle, ItemPtr, Lens, Model, PropSet,
+    ScrollView, TreeExt, Units::*, VStack, View, WindowEvent,
+};
+
+#[derive(Debug)]
+enum VirtualListEvent {
+    // The length of the list has changed
+    SetLen(usize),
+    // The list has been scrolled to a vertical distance
+    Scroll(f32),
+}
+
+/// A view for a long list of items which only builds the rows that are visible.
+///
+/// Every row has the same height, so the position of each row is known without building it. As
+/// the list is scrolled, rows which leave the view are hidden and reused for the rows which come
+/// into view, so the number of rows which exist is limited by the height of the list rather than
+/// by the number of items. The scroll position is kept when the items change.
+///
+/// # Example
+/// ```ignore
+/// VirtualList::new(cx, AppData::samples, 24.0, |cx, item| {
+///     Label::new(cx, &item.get(cx).to_string());
+/// });
+/// ```
+pub struct VirtualList<L, T: 'static>
+where
+    L: Lens<Target = Vec<T>>,
+    T: Data,
+{
+    lens: L,
+    item: Rc<dyn Fn(&mut Context, ItemPtr<L, T>)>,
+    row_height: f32,
+    len: usize,
+    scroll_y: f32,
+    // The container of the rows, with the height of all of the rows
+    rows: Entity,
+    // The rows which have been built, and the index of the item which each row shows
+    pool: Vec<(Entity, Option<usize>)>,
+}
+
+impl<L: 'static + Lens<Target = Vec<T>>, T: Data> VirtualList<L, T> {
+    /// Creates a new virtual list with a binding to the given lens, the height of each row, and a
+    /// template for constructing the list items.
+    pub fn new<F>(cx: &mut Context, lens: L, row_height: f32, item: F) -> Handle<Self>
+    where
+        F: 'static + Fn(&mut Context, ItemPtr<L, T>),
+        <L as Lens>::Source: Model,
+    {
+        let handle = Self {
+            lens: lens.clone(),
+            item: Rc::new(item),
+            row_height: row_height.max(1.0),
+            len: 0,
+            scroll_y: 0.0,
+            rows: Entity::null(),
+            pool: Vec::new(),
+        }
+        .build2(cx, move |cx| {
+            // Only the length of the list is needed here, the rows bind to their own items
+            Binding::new(cx, lens, |cx, list| {
+                let len = list.get(cx).len();
+                cx.emit(VirtualListEvent::SetLen(len));
+            });
+
+            ScrollView::new(cx, false, true, |cx| {
+                VStack::new(cx, |_| {}).class("virtual_rows").height(Pixels(0.0));
+            })
+            .on_scroll(|cx, _, y| cx.emit(VirtualListEvent::Scroll(y)));
+        });
+
+        let rows = handle
+            .entity
+            .child_iter(&handle.cx.tree)
+            .find(|child| {
+                handle
+                    .cx
+                    .views
+                    .get(child)
+                    .map_or(false, |view| view.downcast_ref::<ScrollView>().is_some())
+            })
+            .and_then(|scroll_view| scroll_view.child_iter(&handle.cx.tree).next())
+            .and_then(|content| content.child_iter(&handle.cx.tree).next());
+
+        if let Some(view) = handle.cx.views.get_mut(&handle.entity) {
+            if let Some(list) = view.downcast_mut::<VirtualList<L, T>>() {
+                list.rows = rows.unwrap_or(Entity::null());
+            }
+        }
+
+        handle
+    }
+
+    // Builds the rows which intersect the view and hides the rest
+    fn update(&mut self, cx: &mut Context) {
+        if self.rows == Entity::null() {
+            return;
+        }
+
+        let height = Pixels(self.len as f32 * self.row_height);
+        if cx.style.height.get(self.rows) != Some(&height) {
+            self.rows.set_height(cx, height);
+        }
+
+        let viewport = cx.cache.get_height(cx.current);
+        let first = ((self.scroll_y / self.row_height).floor() as usize).min(self.len);
+        let last = (((self.scroll_y + viewport) / self.row_height).ceil() as usize).min(self.len);
+
+        // Free the rows which have left the view
+        for (row, index) in self.pool.iter_mut() {
+            if let Some(i) = *index {
+                if i < first || i >= last {
+                    *index = None;
+                    row.set_display(cx, Display::None);
+                }
+            }
+        }
+
+        for index in first..last {
+            if self.pool.iter().any(|(_, i)| *i == Some(index)) {
+                continue;
+            }
+
+            // Reuse a free row, or build a new one if there are none
+            let slot = match self.pool.iter().position(|(_, i)| i.is_none()) {
+                Some(slot) => slot,
+                None => {
+                    let row_height = self.row_height;
+                    let row = cx.with_current(self.rows, |cx| {
+                        VStack::new(cx, |_| {})
+                            .class("virtual_row")
+                            .position_type(PositionType::SelfDirected)
+                            .height(Pixels(row_height))
+                            .entity
+                    });
+
+                    self.pool.push((row, None));
+                    self.pool.len() - 1
+                }
+            };
+
+            self.pool[slot].1 = Some(index);
+            let row = self.pool[slot].0;
+            self.build_row(cx, row, index);
+        }
+    }
+
+    // Shows the item at the index in a row, reusing the views which the row already has
+    fn build_row(&self, cx: &mut Context, row: Entity, index: usize) {
+        row.set_top(cx, Pixels(index as f32 * self.row_height)).set_display(cx, Display::Flex);
+
+        let lens = self.lens.clone();
+        let item = self.item.clone();
+
+        cx.with_current(row, |cx| {
+            // Building from the first child replaces the builder of the existing row binding,
+            // so the template rebinds the views of the row to the new index instead of adding more
+            cx.count = 0;
+
+            Binding::new(cx, lens.clone(), move |cx, list| {
+                if index < list.get(cx).len() {
+                    (item)(cx, ItemPtr::new(lens.clone(), index, index, 0));
+                }
+            });
+        });
+    }
+}
+
+impl<L: 'static + Lens<Target = Vec<T>>, T: Data> View for VirtualList<L, T> {
+    fn element(&self) -> Option<String> {
+        Some("virtuallist".to_string())
+    }
+
+    fn event(&mut self, cx: &mut Context, event: &mut Event) {
+        if let Some(virtual_list_event) = event.message.downcast() {
+            match virtual_list_event {
+                VirtualListEvent::SetLen(len) => {
+                    self.len = *len;
+                }
+
+                VirtualListEvent::Scroll(scroll_y) => {
+                    self.scroll_y = *scroll_y;
+                }
+            }
+
+            self.update(cx);
+            event.consume();
+        }
+
+        if let Some(WindowEvent::GeometryChanged(_)) = event.message.downcast() {
+            if event.target == cx.current {
+                self.update(cx);
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use crate::{CursorIcon, MouseButton};
 
 use keyboard_types::{Code, Key};
@@ -40,6 +42,12 @@ pub enum WindowEvent {
     KeyDown(Code, Option<Key>),
     /// Emitted when a keyboard key is released
     KeyUp(Code, Option<Key>),
+    /// Emitted when a file is dragged over the window from another application
+    FileHovered(PathBuf),
+    /// Emitted when a file is dropped onto the window from another application
+    FileDropped(PathBuf),
+    /// Emitted when files which were dragged over the window leave it without being dropped
+    FileHoverCancelled,
     /// Sets the mouse cursor icon
     SetCursor(CursorIcon),
     /// Grabs the mouse cursor, preventing it from leaving the window
@@ -0,0 +1,146 @@
+use vizia_core::*;
+
+static DEFAULT_THEME: &str = include_str!("../../core/src/default_theme.css");
+
+/// An application which runs the frame pipeline without a window or a renderer.
+///
+/// The headless application has a fixed window size and is driven manually by calling
+/// [Application::run_frame]. Events can be pushed into the context event queue between
+/// frames, which makes it possible to test UI logic and layout on machines without a display.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::*;
+/// # use vizia_headless::Application;
+/// let mut app = Application::new(WindowDescription::new().with_inner_size(400, 300), |cx|{
+///     Label::new(cx, "Hello");
+/// });
+///
+/// app.run_frame();
+///
+/// assert_eq!(app.context().cache.get_width(Entity::root()), 400.0);
+/// ```
+pub struct Application {
+    context: Context,
+    runtime: Runtime,
+    builder: Option<Box<dyn Fn(&mut Context)>>,
+    on_idle: Option<Box<dyn Fn(&mut Context)>>,
+}
+
+impl Application {
+    pub fn new<F>(window_description: WindowDescription, builder: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let mut context = Context::new();
+
+        context.entity_manager.create();
+
+        context.add_theme(DEFAULT_THEME);
+
+        let mut runtime = Runtime::new();
+
+        runtime.init(
+            &mut context,
+            window_description.inner_size.width,
+            window_description.inner_size.height,
+        );
+
+        Self { context, runtime, builder: Some(Box::new(builder)), on_idle: None }
+    }
+
+    /// Takes a closure which will be called at the end of every frame.
+    pub fn on_idle<F: 'static + Fn(&mut Context)>(mut self, callback: F) -> Self {
+        self.on_idle = Some(Box::new(callback));
+
+        self
+    }
+
+    pub fn background_color(mut self, color: Color) -> Self {
+        self.context.style.background_color.insert(Entity::root(), color);
+
+        self
+    }
+
+    pub fn locale(mut self, id: &str) -> Self {
+        self.context.enviroment.set_locale(id);
+
+        self
+    }
+
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
+    /// Passes a window event to the application, as a backend would when translating an OS event.
+    ///
+    /// The event is routed on the next call to [Application::run_frame].
+    pub fn handle_event(&mut self, event: WindowEvent) {
+        self.runtime.handle_event(&mut self.context, event);
+    }
+
+    /// Sets the size of the simulated window and emits a `WindowResize` event to the root.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.handle_event(WindowEvent::WindowResize(width as f32, height as f32));
+    }
+
+    /// Runs a single frame of the application.
+    ///
+    /// This rebuilds the application if required, flushes the event queue, updates observers of
+    /// model data, and then runs the style, layout, transform, hover and clipping passes.
+    pub fn run_frame(&mut self) {
+        self.runtime.rebuild(&mut self.context, self.builder.as_deref());
+
+        self.runtime.load_fonts(&mut self.context, None);
+
+        // There is nothing to draw to, so the redraw flag is ignored
+        self.runtime.on_frame_update(&mut self.context);
+
+        self.runtime.on_idle(&mut self.context, self.on_idle.as_deref());
+    }
+
+    /// Draws the application into a software canvas the size of the window.
+    #[cfg(feature = "software")]
+    pub fn render(&mut self) -> SoftwareCanvas {
+        let mut canvas = SoftwareCanvas::new(
+            (self.context.cache.get_width(Entity::root()) as u32).max(1),
+            (self.context.cache.get_height(Entity::root()) as u32).max(1),
+        );
+
+        self.runtime.render_software(&mut self.context, &mut canvas);
+
+        canvas
+    }
+
+    /// Runs frames until the event queue is empty, up to a maximum number of frames.
+    ///
+    /// Returns the number of frames which were run.
+    pub fn run_until_idle(&mut self, max_frames: usize) -> usize {
+        let mut frames = 0;
+
+        loop {
+            self.run_frame();
+            frames += 1;
+
+            if self.context.event_queue.is_empty() || frames >= max_frames {
+                return frames;
+            }
+        }
+    }
+}
+
+impl Env for Application {
+    fn ignore_default_styles(mut self) -> Self {
+        if self.context.enviroment.include_default_theme {
+            self.context.enviroment.include_default_theme = false;
+            self.context.enviroment.needs_rebuild = true;
+            self.context.reload_styles().expect("Failed to reload styles");
+        }
+
+        self
+    }
+}
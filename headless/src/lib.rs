@@ -0,0 +1,5 @@
+mod application;
+mod test_app;
+
+pub use application::Application;
+pub use test_app::TestApp;
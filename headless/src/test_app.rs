@@ -0,0 +1,199 @@
+use vizia_core::*;
+
+use crate::Application;
+
+/// A headless application with simulated input, for testing UI logic.
+///
+/// Entities can be found with css selectors, such as `button.outlined` or `#save`, and input can be
+/// simulated with methods such as [TestApp::click] and [TestApp::type_text]. Each method which
+/// simulates input advances the application by at least one frame.
+///
+/// # Example
+/// ```no_run
+/// # use vizia_core::*;
+/// # use vizia_headless::TestApp;
+/// let mut app = TestApp::new(|cx| {
+///     Button::new(cx, |_| println!("Pressed"), |cx| Label::new(cx, "Press Me")).id("press");
+/// });
+///
+/// let button = app.find("#press");
+/// app.click(button);
+/// ```
+pub struct TestApp {
+    app: Application,
+}
+
+impl TestApp {
+    /// Creates a new test application with the default window size and runs the first frame.
+    pub fn new<F>(builder: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        Self::with_window_description(WindowDescription::new(), builder)
+    }
+
+    /// Creates a new test application with the given window description and runs the first frame.
+    pub fn with_window_description<F>(window_description: WindowDescription, builder: F) -> Self
+    where
+        F: 'static + Fn(&mut Context),
+    {
+        let mut test_app = Self { app: Application::new(window_description, builder) };
+
+        test_app.run_frame();
+
+        test_app
+    }
+
+    pub fn context(&self) -> &Context {
+        self.app.context()
+    }
+
+    pub fn context_mut(&mut self) -> &mut Context {
+        self.app.context_mut()
+    }
+
+    /// Advances the application by a single frame.
+    pub fn run_frame(&mut self) {
+        self.app.run_frame();
+    }
+
+    /// Returns all of the entities which match the selector, in tree order.
+    ///
+    /// # Panics
+    /// Panics if the selector cannot be parsed.
+    pub fn query(&self, selector: &str) -> Vec<Entity> {
+        query_selector(self.context(), selector)
+            .unwrap_or_else(|error| panic!("Invalid selector `{}`: {}", selector, error))
+    }
+
+    /// Returns the first entity which matches the selector.
+    ///
+    /// # Panics
+    /// Panics if no entity matches the selector.
+    pub fn find(&self, selector: &str) -> Entity {
+        self.query(selector)
+            .first()
+            .copied()
+            .unwrap_or_else(|| panic!("No entity matches the selector `{}`", selector))
+    }
+
+    /// Returns the text of an entity, such as the text of a label.
+    pub fn text(&self, entity: Entity) -> Option<&String> {
+        self.context().style.text.get(entity)
+    }
+
+    /// Renders the application with the software renderer and returns the image as
+    /// non-premultiplied RGBA bytes, along with its width and height.
+    #[cfg(feature = "software")]
+    pub fn screenshot(&mut self) -> (Vec<u8>, u32, u32) {
+        let canvas = self.app.render();
+
+        (canvas.to_rgba(), canvas.width(), canvas.height())
+    }
+
+    /// Resizes the simulated window.
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.app.resize(width, height);
+        self.run_frame();
+    }
+
+    /// Moves the mouse cursor to the given position.
+    pub fn mouse_move(&mut self, x: f32, y: f32) {
+        self.send_event(WindowEvent::MouseMove(x, y));
+    }
+
+    /// Presses a mouse button at the current cursor position.
+    pub fn mouse_down(&mut self, button: MouseButton) {
+        self.send_event(WindowEvent::MouseDown(button));
+    }
+
+    /// Releases a mouse button at the current cursor position.
+    pub fn mouse_up(&mut self, button: MouseButton) {
+        self.send_event(WindowEvent::MouseUp(button));
+    }
+
+    /// Clicks the left mouse button at the center of the cached bounds of an entity.
+    pub fn click(&mut self, entity: Entity) {
+        self.click_with(entity, MouseButton::Left);
+    }
+
+    /// Clicks a mouse button at the center of the cached bounds of an entity.
+    pub fn click_with(&mut self, entity: Entity, button: MouseButton) {
+        let (x, y) = self.center(entity);
+
+        self.mouse_move(x, y);
+        self.mouse_down(button);
+        self.mouse_up(button);
+    }
+
+    /// Drags an entity with the left mouse button and releases it over another entity.
+    pub fn drag(&mut self, from: Entity, to: Entity) {
+        let (from_x, from_y) = self.center(from);
+        let (to_x, to_y) = self.center(to);
+
+        self.mouse_move(from_x, from_y);
+        self.mouse_down(MouseButton::Left);
+        self.mouse_move(to_x, to_y);
+        self.mouse_up(MouseButton::Left);
+    }
+
+    /// Drops a file from another application onto the center of the cached bounds of an entity.
+    pub fn drop_file(&mut self, entity: Entity, path: impl Into<std::path::PathBuf>) {
+        let (x, y) = self.center(entity);
+        let path = path.into();
+
+        self.mouse_move(x, y);
+        self.send_event(WindowEvent::FileHovered(path.clone()));
+        self.send_event(WindowEvent::FileDropped(path));
+    }
+
+    /// Scrolls the mouse wheel over the center of the cached bounds of an entity.
+    pub fn scroll(&mut self, entity: Entity, x: f32, y: f32) {
+        let (cursor_x, cursor_y) = self.center(entity);
+
+        self.mouse_move(cursor_x, cursor_y);
+        self.send_event(WindowEvent::MouseScroll(x, y));
+    }
+
+    /// Types a string into the focused entity.
+    ///
+    /// Each character generates a `KeyDown`, `CharInput` and `KeyUp` event. Apart from the space
+    /// character, the code of the generated key events is `Code::Unidentified`.
+    pub fn type_text(&mut self, text: &str) {
+        for character in text.chars() {
+            let code = if character == ' ' { Code::Space } else { Code::Unidentified };
+            let key = Some(Key::Character(character.to_string()));
+
+            self.app.handle_event(WindowEvent::KeyDown(code, key.clone()));
+            self.app.handle_event(WindowEvent::CharInput(character));
+            self.app.handle_event(WindowEvent::KeyUp(code, key));
+        }
+
+        self.run_frame();
+    }
+
+    /// Presses and releases a key while holding the given modifiers, e.g. `Ctrl+S`.
+    pub fn key_chord(&mut self, modifiers: Modifiers, code: Code) {
+        let prev_modifiers = self.context().modifiers;
+
+        self.context_mut().modifiers = modifiers;
+
+        self.send_event(WindowEvent::KeyDown(code, None));
+        self.send_event(WindowEvent::KeyUp(code, None));
+
+        self.context_mut().modifiers = prev_modifiers;
+    }
+
+    // Sends a window event and advances the application by a frame
+    fn send_event(&mut self, event: WindowEvent) {
+        self.app.handle_event(event);
+        self.run_frame();
+    }
+
+    // Returns the center of the cached bounds of an entity
+    fn center(&self, entity: Entity) -> (f32, f32) {
+        let bounds = self.context().cache.get_bounds(entity);
+
+        (bounds.x + bounds.w / 2.0, bounds.y + bounds.h / 2.0)
+    }
+}
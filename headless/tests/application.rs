@@ -0,0 +1,44 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use vizia_core::*;
+use vizia_headless::Application;
+
+fn find(app: &Application, selector: &str) -> Entity {
+    query_selector(app.context(), selector).expect("Invalid selector")[0]
+}
+
+#[test]
+fn frame_lays_out_views_in_the_window() {
+    let mut app = Application::new(WindowDescription::new().with_inner_size(400, 300), |cx| {
+        Element::new(cx).width(Stretch(1.0)).height(Pixels(100.0)).id("header");
+        Element::new(cx).width(Stretch(1.0)).height(Stretch(1.0)).id("body");
+    });
+
+    app.run_frame();
+
+    let body = app.context().cache.get_bounds(find(&app, "#body"));
+    assert_eq!((body.x, body.y, body.w, body.h), (0.0, 100.0, 400.0, 200.0));
+
+    app.resize(200, 500);
+    app.run_frame();
+
+    let body = app.context().cache.get_bounds(find(&app, "#body"));
+    assert_eq!((body.w, body.h), (200.0, 400.0));
+}
+
+#[test]
+fn idle_callback_runs_every_frame() {
+    let idle_count = Rc::new(Cell::new(0));
+    let counter = idle_count.clone();
+
+    let mut app = Application::new(WindowDescription::new(), |cx| {
+        Element::new(cx);
+    })
+    .on_idle(move |_| counter.set(counter.get() + 1));
+
+    let frames = app.run_until_idle(10);
+
+    assert_eq!(idle_count.get(), frames);
+    assert!(app.context().event_queue.is_empty());
+}
@@ -0,0 +1,61 @@
+//! Fixtures which are shared by the headless tests.
+
+#![allow(dead_code)]
+
+use vizia_core::*;
+
+#[derive(Lens)]
+pub struct CounterData {
+    pub count: i32,
+}
+
+#[derive(Debug)]
+pub enum CounterEvent {
+    Increment,
+}
+
+impl Model for CounterData {
+    fn event(&mut self, _: &mut Context, event: &mut Event) {
+        if let Some(counter_event) = event.message.downcast() {
+            match counter_event {
+                CounterEvent::Increment => self.count += 1,
+            }
+        }
+    }
+}
+
+pub fn counter(cx: &mut Context) {
+    CounterData { count: 0 }.build(cx);
+
+    Button::new(cx, |cx| cx.emit(CounterEvent::Increment), |cx| Label::new(cx, "Increment"))
+        .id("increment")
+        .class("outlined");
+
+    Binding::new(cx, CounterData::count, |cx, count| {
+        Label::new(cx, &count.get(cx).to_string()).id("count");
+    });
+}
+
+#[derive(Lens)]
+pub struct SampleData {
+    pub samples: Vec<u32>,
+}
+
+#[derive(Debug)]
+pub enum SampleEvent {
+    Insert(usize, u32),
+    Remove(usize),
+}
+
+impl Model for SampleData {
+    fn event(&mut self, _: &mut Context, event: &mut Event) {
+        if let Some(sample_event) = event.message.downcast() {
+            match sample_event {
+                SampleEvent::Insert(index, sample) => self.samples.insert(*index, *sample),
+                SampleEvent::Remove(index) => {
+                    self.samples.remove(*index);
+                }
+            }
+        }
+    }
+}
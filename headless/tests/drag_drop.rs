@@ -0,0 +1,41 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn drop_payload_onto_target() {
+    let mut app = TestApp::new(|cx| {
+        counter(cx);
+
+        Element::new(cx).size(Pixels(50.0)).id("source").draggable(|_| CounterEvent::Increment);
+
+        Element::new(cx)
+            .size(Pixels(50.0))
+            .id("target")
+            .on_drop(|cx, counter_event: CounterEvent| cx.emit(counter_event));
+    });
+
+    app.drag(app.find("#source"), app.find("#target"));
+
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+    assert!(!app.context().drag.is_dragging());
+}
+
+#[test]
+fn drop_file_onto_view() {
+    let mut app = TestApp::new(|cx| {
+        counter(cx);
+
+        Element::new(cx)
+            .size(Pixels(50.0))
+            .id("sampler")
+            .on_file_drop(|cx, _| cx.emit(CounterEvent::Increment));
+    });
+
+    app.drop_file(app.find("#sampler"), "kick.wav");
+
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+}
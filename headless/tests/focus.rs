@@ -0,0 +1,84 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn tab_moves_focus() {
+    let mut app = TestApp::new(|cx| {
+        Button::new(cx, |_| {}, |cx| Label::new(cx, "First")).id("first");
+        Button::new(cx, |_| {}, |cx| Label::new(cx, "Second")).id("second");
+    });
+
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    assert_eq!(app.context().focused, app.find("#first"));
+
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    assert_eq!(app.context().focused, app.find("#second"));
+
+    app.key_chord(Modifiers::SHIFT, Code::Tab);
+    assert_eq!(app.context().focused, app.find("#first"));
+}
+
+#[test]
+fn enter_activates_focused_button() {
+    let mut app = TestApp::new(counter);
+
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    app.key_chord(Modifiers::empty(), Code::Enter);
+
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+}
+
+#[test]
+fn clicking_a_label_focuses_its_button() {
+    let mut app = TestApp::new(|cx| {
+        Button::new(cx, |_| {}, |cx| Label::new(cx, "Press").id("label")).id("button");
+    });
+
+    app.click(app.find("#label"));
+
+    assert_eq!(app.context().focused, app.find("#button"));
+}
+
+struct Swatch {}
+
+impl View for Swatch {}
+
+#[test]
+fn custom_views_are_only_tabbable_once_focusable() {
+    let mut app = TestApp::new(|cx| {
+        VStack::new(cx, |cx| {
+            Label::new(cx, "Color");
+            Swatch {}.build(cx).id("plain").size(Pixels(20.0));
+            Swatch {}.build(cx).id("swatch").size(Pixels(20.0)).focusable(true);
+        });
+    });
+
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    assert_eq!(app.context().focused, app.find("#swatch"));
+
+    // Focus wraps around to the same view, since it is the only one in the focus order
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    assert_eq!(app.context().focused, app.find("#swatch"));
+}
+
+#[test]
+fn pressable_label_is_activated_from_the_keyboard() {
+    let mut app = TestApp::new(|cx| {
+        CounterData { count: 0 }.build(cx);
+
+        Label::new(cx, "Increment").on_press(|cx| cx.emit(CounterEvent::Increment));
+
+        Binding::new(cx, CounterData::count, |cx, count| {
+            Label::new(cx, &count.get(cx).to_string()).id("count");
+        });
+    });
+
+    app.key_chord(Modifiers::empty(), Code::Tab);
+    app.key_chord(Modifiers::empty(), Code::Space);
+
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+}
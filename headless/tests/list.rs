@@ -0,0 +1,95 @@
+mod common;
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn keyed_list_keeps_rows_of_moved_items() {
+    let mut app = TestApp::new(|cx| {
+        SampleData { samples: vec![1, 2, 3] }.build(cx);
+
+        Button::new(cx, |cx| cx.emit(SampleEvent::Insert(0, 0)), |cx| Label::new(cx, "Insert"))
+            .id("insert");
+        Button::new(cx, |cx| cx.emit(SampleEvent::Remove(1)), |cx| Label::new(cx, "Remove"))
+            .id("remove");
+
+        List::new_keyed(
+            cx,
+            SampleData::samples,
+            |sample| *sample,
+            |cx, item| {
+                Label::new(cx, &item.get(cx).to_string());
+            },
+        );
+    });
+
+    let texts = |app: &TestApp| {
+        app.query("list label")
+            .into_iter()
+            .filter_map(|label| app.text(label).cloned())
+            .collect::<Vec<_>>()
+    };
+
+    let rows = app.query("list label");
+
+    app.click(app.find("#insert"));
+    assert_eq!(texts(&app), vec!["0", "1", "2", "3"]);
+    assert_eq!(app.query("list label")[1..], rows[..]);
+
+    app.click(app.find("#remove"));
+    assert_eq!(texts(&app), vec!["0", "2", "3"]);
+    assert_eq!(app.query("list label")[1..], rows[1..]);
+}
+
+#[test]
+fn keyed_list_rows_follow_their_items() {
+    let builds = Rc::new(Cell::new(0));
+    let counter = builds.clone();
+
+    let mut app = TestApp::new(move |cx| {
+        SampleData { samples: vec![1, 2, 3] }.build(cx);
+
+        Button::new(cx, |cx| cx.emit(SampleEvent::Insert(0, 0)), |cx| Label::new(cx, "Insert"))
+            .id("insert");
+
+        let counter = counter.clone();
+        List::new_keyed(
+            cx,
+            SampleData::samples,
+            |sample| *sample,
+            move |cx, item| {
+                counter.set(counter.get() + 1);
+
+                // Removes the item of the row, wherever the row has been moved to
+                Button::new(
+                    cx,
+                    move |cx| cx.emit(SampleEvent::Remove(item.current_index(cx))),
+                    move |cx| Label::new(cx, &item.get(cx).to_string()),
+                );
+            },
+        );
+    });
+
+    let texts = |app: &TestApp| {
+        app.query("list label")
+            .into_iter()
+            .filter_map(|label| app.text(label).cloned())
+            .collect::<Vec<_>>()
+    };
+
+    app.click(app.find("#insert"));
+    assert_eq!(texts(&app), vec!["0", "1", "2", "3"]);
+
+    // Only the inserted row was built
+    assert_eq!(builds.get(), 4);
+
+    // The row of the item "2" was built at index 1 and has since moved to index 2
+    app.click(app.query("list button")[2]);
+    assert_eq!(texts(&app), vec!["0", "1", "3"]);
+    assert_eq!(builds.get(), 4);
+}
@@ -0,0 +1,58 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn context_menu_opens_on_right_click() {
+    let mut app = TestApp::new(|cx| {
+        counter(cx);
+
+        Element::new(cx).size(Pixels(50.0)).id("clip").context_menu(|cx| {
+            MenuItem::new(cx, "Increment", |cx| cx.emit(CounterEvent::Increment))
+                .id("increment-item");
+            MenuSeparator::new(cx);
+            MenuItem::new(cx, "Disabled", |cx| cx.emit(CounterEvent::Increment)).disabled(true);
+        });
+    });
+
+    app.click_with(app.find("#clip"), MouseButton::Right);
+    assert_eq!(app.query("menu").len(), 1);
+    assert_eq!(app.context().focused, app.find("#increment-item"));
+
+    app.key_chord(Modifiers::empty(), Code::Enter);
+    assert!(app.query("menu").is_empty());
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+
+    app.click_with(app.find("#clip"), MouseButton::Right);
+    app.key_chord(Modifiers::empty(), Code::Escape);
+    assert!(app.query("menu").is_empty());
+}
+
+#[test]
+fn menu_bar_shortcut_and_toggle() {
+    let mut app = TestApp::new(|cx| {
+        counter(cx);
+
+        MenuBar::new(cx, |cx| {
+            Menu::new(cx, "File", |cx| {
+                MenuItem::new(cx, "Increment", |cx| cx.emit(CounterEvent::Increment))
+                    .shortcut("increment", "Ctrl+I");
+            })
+            .id("file");
+        });
+    });
+
+    app.key_chord(Modifiers::CTRL, Code::KeyI);
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+    assert!(!app.context().menus.is_open());
+
+    app.click(app.find("#file"));
+    assert!(app.context().menus.is_open());
+    assert_eq!(app.text(app.find("menuitem label.shortcut")), Some(&"Ctrl+I".to_string()));
+
+    app.click(app.find("#file"));
+    assert!(!app.context().menus.is_open());
+}
@@ -0,0 +1,52 @@
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+#[test]
+fn portal_flips_and_shifts_inside_window() {
+    let app = TestApp::new(|cx| {
+        VStack::new(cx, |cx| {
+            let anchor = cx.current;
+            Portal::new(cx, anchor, |cx| {
+                Element::new(cx).size(Pixels(100.0)).id("content");
+            });
+        })
+        .left(Pixels(760.0))
+        .top(Pixels(560.0))
+        .size(Pixels(30.0));
+    });
+
+    let bounds = app.context().cache.get_bounds(app.find("#content"));
+    assert_eq!((bounds.x, bounds.y), (700.0, 460.0));
+
+    // The content is not clipped by the 30px anchor which contains it
+    let clip_region = app.context().cache.get_clip_region(app.find("#content"));
+    assert_eq!((clip_region.w, clip_region.h), (100.0, 100.0));
+}
+
+#[test]
+fn popup_placement_follows_window_resize() {
+    let mut app = TestApp::new(|cx| {
+        PopupData::default().build(cx);
+        cx.emit(PopupEvent::Open);
+
+        VStack::new(cx, |cx| {
+            Popup::new(cx, |cx| {
+                Element::new(cx).size(Pixels(100.0));
+            })
+            .placement(Placement::Right)
+            .size(Auto)
+            .id("popup");
+        })
+        .left(Pixels(500.0))
+        .size(Pixels(50.0));
+    });
+
+    app.run_frame();
+    let bounds = app.context().cache.get_bounds(app.find("#popup"));
+    assert_eq!((bounds.x, bounds.y), (550.0, 0.0));
+
+    // Flipped to the left of the anchor when there is no space on the right
+    app.resize(600, 600);
+    let bounds = app.context().cache.get_bounds(app.find("#popup"));
+    assert_eq!((bounds.x, bounds.y), (400.0, 0.0));
+}
@@ -0,0 +1,129 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+// Builds a vertical scroll view with ten items, which are five times the height of the view
+fn scroll_view(cx: &mut Context) -> Handle<ScrollView> {
+    ScrollView::new(cx, false, true, |cx| {
+        for i in 0..10 {
+            Element::new(cx).height(Pixels(50.0)).id(&format!("item{}", i));
+        }
+    })
+    .size(Pixels(100.0))
+    .id("scroll")
+}
+
+#[derive(Lens)]
+struct ScrollModel {
+    scroll: f32,
+}
+
+#[derive(Debug)]
+enum ScrollModelEvent {
+    SetScroll(f32),
+}
+
+impl Model for ScrollModel {
+    fn event(&mut self, _: &mut Context, event: &mut Event) {
+        if let Some(ScrollModelEvent::SetScroll(scroll)) = event.message.downcast() {
+            self.scroll = *scroll;
+        }
+    }
+}
+
+#[test]
+fn scroll_view_scrolls_with_wheel_and_scroll_to() {
+    let mut app = TestApp::new(|cx| {
+        scroll_view(cx);
+    });
+
+    app.scroll(app.find("#scroll"), 0.0, -1.0);
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), -35.0);
+
+    let item = app.find("#item9");
+    scroll_to(app.context_mut(), item);
+    app.run_frame();
+    assert_eq!(app.context().cache.get_posy(item), 50.0);
+}
+
+// The thumb of the vertical scrollbar is 20px long, so each pixel it moves scrolls 5px
+#[test]
+fn dragging_the_thumb_scrolls() {
+    let mut app = TestApp::new(|cx| {
+        scroll_view(cx);
+    });
+    app.run_frame();
+
+    let thumb = app.context().cache.get_bounds(app.find("scrollbar.vertical .thumb"));
+    assert_eq!(thumb.h, 20.0);
+
+    let x = thumb.x + thumb.w / 2.0;
+    app.mouse_move(x, thumb.y + 10.0);
+    app.mouse_down(MouseButton::Left);
+    app.mouse_move(x, thumb.y + 20.0);
+    app.mouse_up(MouseButton::Left);
+
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), -50.0);
+}
+
+#[test]
+fn pressing_the_track_centers_the_thumb_on_the_cursor() {
+    let mut app = TestApp::new(|cx| {
+        scroll_view(cx);
+    });
+    app.run_frame();
+
+    let track = app.context().cache.get_bounds(app.find("scrollbar.vertical"));
+
+    app.mouse_move(track.x + track.w / 2.0, track.y + 70.0);
+    app.mouse_down(MouseButton::Left);
+    app.mouse_up(MouseButton::Left);
+
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), -300.0);
+}
+
+#[test]
+fn smooth_scrolling_animates_to_the_target() {
+    let mut app = TestApp::new(|cx| {
+        scroll_view(cx).smooth(true);
+    });
+
+    app.scroll(app.find("#scroll"), 0.0, -1.0);
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), 0.0);
+
+    let start = Instant::now();
+    let mut steps = Vec::new();
+    while steps.last() != Some(&-35.0) {
+        assert!(start.elapsed() < Duration::from_secs(5), "Smooth scrolling did not finish");
+
+        thread::sleep(Duration::from_millis(16));
+        app.run_frame();
+        steps.push(app.context().cache.get_posy(app.find("#item0")));
+    }
+
+    // The content moved towards the target over several frames without overshooting it
+    assert!(steps.iter().any(|posy| *posy < 0.0 && *posy > -35.0));
+    assert!(steps.iter().all(|posy| *posy >= -35.0));
+}
+
+#[test]
+fn scroll_position_follows_the_model() {
+    let mut app = TestApp::new(|cx| {
+        ScrollModel { scroll: 100.0 }.build(cx);
+
+        Binding::new(cx, ScrollModel::scroll, |cx, scroll| {
+            scroll_view(cx).scroll_y(scroll);
+        });
+    });
+    app.run_frame();
+
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), -100.0);
+
+    app.context_mut().event_queue.push_back(Event::new(ScrollModelEvent::SetScroll(200.0)));
+    app.run_frame();
+    app.run_frame();
+
+    assert_eq!(app.context().cache.get_posy(app.find("#item0")), -200.0);
+}
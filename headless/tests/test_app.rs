@@ -0,0 +1,26 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn query_by_selector() {
+    let app = TestApp::new(counter);
+
+    assert_eq!(app.query("button").len(), 1);
+    assert_eq!(app.query("button.outlined"), app.query("#increment"));
+    assert!(app.query("button.missing").is_empty());
+}
+
+#[test]
+fn click_increments_counter() {
+    let mut app = TestApp::new(counter);
+
+    assert_eq!(app.text(app.find("#count")), Some(&"0".to_string()));
+
+    app.click(app.find("#increment"));
+
+    assert_eq!(app.text(app.find("#count")), Some(&"1".to_string()));
+}
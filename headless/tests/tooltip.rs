@@ -0,0 +1,51 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+#[test]
+fn tooltip_shows_after_delay() {
+    let mut app = TestApp::new(|cx| {
+        cx.tooltip.delay = std::time::Duration::ZERO;
+
+        Element::new(cx).size(Pixels(50.0)).id("gain").tooltip("Gain");
+    });
+
+    let bounds = app.context().cache.get_bounds(app.find("#gain"));
+    app.mouse_move(bounds.x + 10.0, bounds.y + 10.0);
+    app.run_frame();
+    assert_eq!(app.query("tooltip").len(), 1);
+
+    app.mouse_down(MouseButton::Left);
+    assert!(app.query("tooltip").is_empty());
+}
+
+#[test]
+fn tooltip_is_removed_when_its_view_is_rebuilt() {
+    let mut app = TestApp::new(|cx| {
+        cx.tooltip.delay = std::time::Duration::ZERO;
+
+        CounterData { count: 0 }.build(cx);
+
+        Binding::new(cx, CounterData::count, |cx, count| {
+            let gain = format!("Gain {}", count.get(cx));
+            Element::new(cx).size(Pixels(50.0)).id("gain").tooltip(gain.as_str());
+        });
+    });
+
+    let bounds = app.context().cache.get_bounds(app.find("#gain"));
+    app.mouse_move(bounds.x + 10.0, bounds.y + 10.0);
+    app.run_frame();
+    assert_eq!(app.query("tooltip").len(), 1);
+
+    app.context_mut().event_queue.push_back(Event::new(CounterEvent::Increment));
+    app.run_frame();
+    assert!(app.query("tooltip").is_empty());
+
+    // The rebuilt view shows its own tooltip once the cursor moves on it again
+    app.mouse_move(bounds.x + 20.0, bounds.y + 20.0);
+    app.run_frame();
+    assert_eq!(app.query("tooltip").len(), 1);
+}
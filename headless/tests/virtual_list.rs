@@ -0,0 +1,70 @@
+mod common;
+
+use vizia_core::*;
+use vizia_headless::TestApp;
+
+use common::*;
+
+// Returns the text of the labels in the rows which are shown
+fn shown(app: &TestApp) -> Vec<String> {
+    app.query(".virtual_row label")
+        .into_iter()
+        .filter(|label| app.context().cache.get_display(*label) != Display::None)
+        .filter_map(|label| app.text(label).cloned())
+        .collect()
+}
+
+#[test]
+fn virtual_list_builds_visible_rows() {
+    let mut app = TestApp::new(|cx| {
+        SampleData { samples: (0..10_000).collect() }.build(cx);
+
+        VirtualList::new(cx, SampleData::samples, 20.0, |cx, item| {
+            Label::new(cx, &item.get(cx).to_string());
+        })
+        .size(Pixels(100.0))
+        .id("list");
+    });
+
+    app.run_frame();
+    assert_eq!(app.query(".virtual_row").len(), 5);
+
+    app.scroll(app.find("#list"), 0.0, -1.0);
+    let texts = shown(&app);
+
+    // Scrolled by 35px, so rows 1 to 6 intersect the 100px view
+    assert_eq!(app.query(".virtual_row").len(), 6);
+    assert!(texts.contains(&"6".to_string()) && !texts.contains(&"0".to_string()));
+}
+
+#[test]
+fn virtual_list_keeps_scroll_position_when_items_change() {
+    let mut app = TestApp::new(|cx| {
+        SampleData { samples: (0..100).collect() }.build(cx);
+
+        VirtualList::new(cx, SampleData::samples, 20.0, |cx, item| {
+            Label::new(cx, &item.get(cx).to_string());
+        })
+        .size(Pixels(100.0))
+        .id("list");
+    });
+
+    app.run_frame();
+    app.scroll(app.find("#list"), 0.0, -1.0);
+
+    let content = app.find(".scroll_content");
+    let offset = app.context().cache.get_posy(content);
+    let labels = app.query(".virtual_row label");
+    assert!(!shown(&app).contains(&"0".to_string()));
+
+    app.context_mut().event_queue.push_back(Event::new(SampleEvent::Insert(0, 1000)));
+    app.run_frame();
+    app.run_frame();
+
+    // The first item moved into view, while the list stayed where it was scrolled to
+    assert_eq!(app.context().cache.get_posy(content), offset);
+    assert!(shown(&app).contains(&"0".to_string()));
+
+    // The rows are rebound to their new items rather than rebuilt
+    assert_eq!(app.query(".virtual_row label"), labels);
+}
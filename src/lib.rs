@@ -4,4 +4,7 @@ pub use vizia_glutin::application::Application;
 #[cfg(all(not(feature = "glutin"), feature = "baseview"))]
 pub use vizia_baseview::{Application, ParentWindow};
 
+#[cfg(feature = "headless")]
+pub use vizia_headless as headless;
+
 pub use vizia_core::*;